@@ -1,7 +1,13 @@
 from ._python_calamine import (
+    CalamineArrowTable,
     CalamineError,
     CalamineSheet,
+    CalamineTable,
     CalamineWorkbook,
+    CellError,
+    CellErrorTypeEnum,
+    CellValueError,
+    DetectedFormat,
     PasswordError,
     SheetMetadata,
     SheetTypeEnum,
@@ -10,13 +16,20 @@ from ._python_calamine import (
     WorksheetNotFound,
     XmlError,
     ZipError,
+    detect_format,
     load_workbook,
 )
 
 __all__ = (
+    "CalamineArrowTable",
     "CalamineError",
     "CalamineSheet",
+    "CalamineTable",
     "CalamineWorkbook",
+    "CellError",
+    "CellErrorTypeEnum",
+    "CellValueError",
+    "DetectedFormat",
     "PasswordError",
     "SheetMetadata",
     "SheetTypeEnum",
@@ -25,5 +38,6 @@ __all__ = (
     "XmlError",
     "ZipError",
     "WorkbookClosed",
+    "detect_format",
     "load_workbook",
 )
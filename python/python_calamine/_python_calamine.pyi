@@ -2,6 +2,7 @@ from __future__ import annotations
 
 import contextlib
 import datetime
+import decimal
 import enum
 import os
 import types
@@ -12,6 +13,8 @@ class ReadBuffer(typing.Protocol):
     def seek(self, __offset: int, __whence: int = ...) -> int: ...
     def read(self, __size: int = ...) -> bytes | None: ...
 
+WorkbookFormat = typing.Literal["xlsx", "xlsm", "xlsb", "xls", "ods"]
+
 @typing.final
 class SheetTypeEnum(enum.Enum):
     WorkSheet = ...
@@ -26,6 +29,24 @@ class SheetVisibleEnum(enum.Enum):
     Hidden = ...
     VeryHidden = ...
 
+@typing.final
+class CellErrorTypeEnum(enum.Enum):
+    Div0 = ...
+    NA = ...
+    Name = ...
+    Null = ...
+    Num = ...
+    Ref = ...
+    Value = ...
+    GettingData = ...
+
+@typing.final
+class CellError:
+    typ: CellErrorTypeEnum
+    text: str
+
+    def __init__(self, typ: CellErrorTypeEnum, text: str) -> None: ...
+
 @typing.final
 class SheetMetadata:
     name: str
@@ -36,6 +57,32 @@ class SheetMetadata:
         self, name: str, typ: SheetTypeEnum, visible: SheetVisibleEnum
     ) -> None: ...
 
+@typing.final
+class DetectedFormat:
+    format: WorkbookFormat | None
+    """Format of file, can be passed as `format` for opening it.
+    `None` for encrypted files and unknown formats."""
+    biff_version: int | None
+    """BIFF version (2, 3, 4, 5 or 8) of xls, `None` for other formats."""
+    encrypted: bool
+    """Password protected xlsx, xlsm or xlsb."""
+
+    def __init__(
+        self,
+        format: WorkbookFormat | None = None,
+        biff_version: int | None = None,
+        encrypted: bool = False,
+    ) -> None: ...
+
+@typing.final
+class CalamineArrowTable:
+    @property
+    def num_rows(self) -> int: ...
+    @property
+    def column_names(self) -> list[str]: ...
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object:
+        """Export data via the Arrow PyCapsule interface."""
+
 @typing.final
 class CalamineSheet:
     name: str
@@ -51,18 +98,113 @@ class CalamineSheet:
     def start(self) -> tuple[int, int] | None: ...
     @property
     def end(self) -> tuple[int, int] | None: ...
+    @property
+    def merged_cells(self) -> list[tuple[tuple[int, int], tuple[int, int]]] | None:
+        """Merged regions of sheet as list of (start, end) tuples.
+
+        `None` if merged regions of sheet are unknown.
+
+        Raises:
+            CalamineError: If sheet was loaded without `merged_cells=True`.
+        """
+
+    def cell(
+        self, row: int, col: int, relative: bool = False
+    ) -> (
+        int
+        | float
+        | str
+        | bool
+        | datetime.time
+        | datetime.date
+        | datetime.datetime
+        | datetime.timedelta
+        | CellError
+        | typing.Any
+    ):
+        """Value of cell, without loading the whole sheet into Python.
+
+        Args:
+            row (int): zero-based row index.
+            col (int): zero-based column index.
+            relative (bool):
+                If `True`, indexes are counted from `start` (as in `to_python()`),
+                otherwise they are absolute (`A1` is `(0, 0)`).
+
+        Raises:
+            IndexError: If cell is out of sub-range created by `range`.
+        """
+
+    def __getitem__(
+        self, key: str | tuple[int, int]
+    ) -> (
+        int
+        | float
+        | str
+        | bool
+        | datetime.time
+        | datetime.date
+        | datetime.datetime
+        | datetime.timedelta
+        | CellError
+        | typing.Any
+    ):
+        """Value of cell by reference like `B7` or absolute `(row, col)`."""
+
+    @typing.overload
+    def range(self, start: str, *, relative: bool = False) -> CalamineSheet: ...
+    @typing.overload
+    def range(
+        self, start: tuple[int, int], end: tuple[int, int], relative: bool = False
+    ) -> CalamineSheet: ...
+    def range(
+        self,
+        start: str | tuple[int, int],
+        end: tuple[int, int] | None = None,
+        relative: bool = False,
+    ) -> CalamineSheet:
+        """Sub-range of sheet as `CalamineSheet`, data isn't copied.
+
+        The range is clipped by data of sheet, so whole rows and columns (`A:C`, `1:3`)
+        are limited by it. The sheet is empty if the range doesn't overlap data.
+
+        Args:
+            start (str | tuple[int, int]): reference like `A1:D20` or (row, col) of top-left cell.
+            end (tuple[int, int] | None): (row, col) of bottom-right cell, if `start` is tuple.
+            relative (bool): If `True`, positions are counted from `start` of sheet, see `cell`.
+
+        Raises:
+            IndexError: If range is out of sub-range created by `range`.
+        """
+
     def to_python(
-        self, skip_empty_area: bool = True, nrows: int | None = None
+        self,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        fill_merged: bool = False,
+        usecols: str | list[int] | list[str] | None = None,
+        skiprows: int | list[int] | typing.Callable[[int], bool] | None = None,
+        skipfooter: int = 0,
+        skip_blank_rows: bool = False,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+        values: typing.Literal["typed", "formatted"] = "typed",
+        floats: typing.Literal["float", "decimal"] = "float",
+        numbers: typing.Literal["keep", "int", "float"] = "keep",
     ) -> list[
         list[
             int
             | float
+            | decimal.Decimal
             | str
             | bool
             | datetime.time
             | datetime.date
             | datetime.datetime
             | datetime.timedelta
+            | CellError
+            | typing.Any
         ]
     ]:
         """Retunrning data from sheet as list of lists.
@@ -71,53 +213,356 @@ class CalamineSheet:
             skip_empty_area (bool):
                 By default, calamine skips empty rows/cols before data.
                 For suppress this behaviour, set `skip_empty_area` to `False`.
+            errors (str):
+                How to return error cells (`#DIV/0!`, `#N/A`, etc.):
+                `value` - as `CellError`, `none` - as `None`,
+                `string` - as error text, `raise` - raise `CellValueError`.
+            empty_value (Any):
+                Value for empty cells. By default, `CalamineWorkbook.empty_value` is used.
+            fill_merged (bool):
+                Copy top-left value of merged region into every cell of the region.
+                Sheet must be loaded with `merged_cells=True`.
+            usecols (str | list[int] | list[str] | None):
+                Columns to return: Excel letters like `A:C,F` (up to `XFD`), zero-based
+                positions of columns in data or list of names from the first row of data
+                (str is always parsed as letters, so a single name is given as `["Qty"]`).
+                Other columns are skipped before converting cells to Python objects.
+            skiprows (int | list[int] | Callable[[int], bool] | None):
+                Number of rows to skip at the top, zero-based indexes of rows to skip
+                or function, which takes index of row and returns `True` to skip it.
+                Indexes are counted from the first returned row (see `skip_empty_area`),
+                `nrows` is counted after skipping.
+            skipfooter (int): Number of rows to skip at the bottom.
+            skip_blank_rows (bool):
+                Skip rows, where all cells (only `usecols`, if set) are empty or blank strings.
+            dates (str | None):
+                How to return dates: `infer` - as `date`, `time`, `datetime` or `timedelta`
+                depending on value, `raw` - Excel dates as serial `float` (ods dates as ISO strings),
+                `datetime` - always as `datetime` (time-only values get Excel epoch date),
+                durations are still `timedelta`. By default, `CalamineWorkbook.dates` is used.
+                ods durations (`PT10H10M10S`, `PT255H10M10S`) are always `timedelta`.
+            values (str):
+                `typed` - cells as Python objects, `formatted` - cells as text rendered
+                with their number format, like Excel displays them (`02134` for ZIP code
+                with format `00000`, `12.34%`, `$1,234.50`). Empty and error cells
+                follow `empty_value` and `errors`. Sheet must be loaded with
+                `number_formats=True`. Fractions and conditional formats are rendered
+                as `General`, names of months and days are English.
+            floats (str):
+                `float` - float cells as `float`, `decimal` - as `decimal.Decimal`
+                from the shortest representation, which round-trips to the float
+                (`0.1` becomes `Decimal("0.1")`).
+            numbers (str):
+                `keep` - numbers as `int` or `float`, as stored in the file,
+                `int` - whole floats in the range of int64 as `int`,
+                `float` - all numbers as `float`. xlsx stores most numbers as floats,
+                so this gives the same types for xls, xlsx and ods.
+        """
+
+    def to_records(
+        self,
+        header_row: int | list[int] = 0,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        fill_merged: bool = False,
+        record_type: typing.Literal["dict", "namedtuple"] = "dict",
+        usecols: str | list[int] | list[str] | None = None,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+    ) -> list[dict[str, typing.Any]] | list[typing.NamedTuple]:
+        """Returning data from sheet as list of records, keys are taken from the header row.
+
+        Blank names become `Unnamed: {index}`, duplicates get `.1`, `.2`, ... suffixes (like pandas).
+
+        Args:
+            header_row (int | list[int]):
+                Index of row with column names (see `to_arrow`), rows before it are skipped.
+                If list, names from several rows are joined by `_`.
+            skip_empty_area (bool): see `to_python`.
+            nrows (int | None): Maximum number of records.
+            errors (str): How to return error cells, see `to_python`.
+            empty_value (Any): Value for empty cells, see `to_python`.
+            fill_merged (bool): Fill merged regions, see `to_python`.
+            record_type (str):
+                `dict` or `namedtuple`, invalid field names of named tuple
+                are replaced with `_{index}`.
+            usecols (str | list[int] | list[str] | None):
+                Columns to return, see `to_python`, names are taken from `header_row`.
+            dates (str | None): How to return dates, see `to_python`.
+        """
+
+    def iter_records(
+        self,
+        header_row: int | list[int] = 0,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        fill_merged: bool = False,
+        record_type: typing.Literal["dict", "namedtuple"] = "dict",
+        usecols: str | list[int] | list[str] | None = None,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+    ) -> typing.Iterator[dict[str, typing.Any]] | typing.Iterator[typing.NamedTuple]:
+        """Returning data from sheet as iterator of records, see `to_records`."""
+
+    def to_columns(
+        self,
+        header_row: int | list[int] | None = None,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        fill_merged: bool = False,
+        usecols: str | list[int] | list[str] | None = None,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+    ) -> list[list[typing.Any]] | dict[str, list[typing.Any]]:
+        """Returning data from sheet as list of columns.
+
+        Args:
+            header_row (int | list[int] | None):
+                If set, dict of columns keyed by column names is returned (see `to_records`),
+                header rows and rows before them are not included.
+            skip_empty_area (bool): see `to_python`.
+            nrows (int | None): Maximum number of rows (after header) in each column.
+            errors (str): How to return error cells, see `to_python`.
+            empty_value (Any): Value for empty cells, see `to_python`.
+            fill_merged (bool): Fill merged regions, see `to_python`.
+            usecols (str | list[int] | list[str] | None):
+                Columns to return, see `to_python`, names are taken from `header_row`.
+            dates (str | None): How to return dates, see `to_python`.
+        """
+
+    def to_numpy(
+        self,
+        dtype: typing.Any = None,
+        usecols: str | list[int] | list[str] | None = None,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        fill_merged: bool = False,
+    ) -> typing.Any:
+        """Returning data from sheet as `numpy.ndarray`, numpy must be installed.
+
+        Empty and error cells are `nan` for floats and `NaT` for dates,
+        integer arrays with missing values are returned as `numpy.ma.MaskedArray`.
+        Strings are parsed as numbers, other values which can't be converted raise `ValueError`.
+
+        Args:
+            dtype (Any):
+                `float32`, `float64`, `int32`, `int64` or `datetime64[s|ms|us|ns]`.
+                By default, columns of dates are `datetime64[us]` and others are `float64`;
+                if both kinds are present, structured array with fields named
+                by column letters is returned (`C.1` for the second `C` in `usecols`).
+            usecols (str | list[int] | list[str] | None): Columns to return, see `to_python`.
+            skip_empty_area (bool): see `to_python`.
+            nrows (int | None): Maximum number of rows.
+            fill_merged (bool): Fill merged regions, see `to_python`.
+        """
+
+    def number_formats(
+        self,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        usecols: str | list[int] | list[str] | None = None,
+    ) -> list[list[str]]:
+        """Retunrning number format codes of cells (`0.00%`, `yyyy-mm-dd`, `@`, etc.)
+        as list of lists, aligned with `to_python`. Unformatted cells are `General`.
+
+        Args:
+            skip_empty_area (bool): see `to_python`.
+            nrows (int | None): see `to_python`.
+            usecols (str | list[int] | list[str] | None): Columns to return, see `to_python`.
+
+        Raises:
+            CalamineError: If sheet was loaded without `number_formats=True`.
+        """
+
+    def formulas(
+        self,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        usecols: str | list[int] | list[str] | None = None,
+    ) -> list[list[str | None]]:
+        """Retunrning formulas from sheet as list of lists, aligned with `to_python`.
+
+        Formulas are returned without leading `=`, `None` for cells without formula.
+
+        Args:
+            skip_empty_area (bool): see `to_python`.
+            nrows (int | None): see `to_python`.
+            usecols (str | list[int] | list[str] | None): Columns to return, see `to_python`.
+
+        Raises:
+            CalamineError: If sheet was loaded without `formulas=True`.
+        """
+
+    def to_arrow(
+        self,
+        header_row: int | None = 0,
+        skip_empty_area: bool = True,
+        usecols: str | list[int] | list[str] | None = None,
+    ) -> CalamineArrowTable:
+        """Returning data from sheet as Arrow table, which can be consumed
+        by pyarrow, polars, duckdb, etc. via the Arrow PyCapsule interface.
+
+        Column types are inferred from cells, columns with mixed types are strings.
+        Dates are converted according to `CalamineWorkbook.dates`.
+
+        Args:
+            header_row (int | None):
+                Index of row with column names, rows before it are skipped.
+                If `None`, columns are named `column_0`, `column_1`, etc.
+            skip_empty_area (bool): see `to_python`.
+            usecols (str | list[int] | list[str] | None):
+                Columns to return, see `to_python`, names are taken from `header_row`.
+        """
+
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object:
+        """Export data via the Arrow PyCapsule interface, same as `to_arrow()`.
+
+        The protocol has no options, use `to_arrow(usecols=...)` to export some columns.
         """
 
     def iter_rows(
         self,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        fill_merged: bool = False,
+        usecols: str | list[int] | list[str] | None = None,
+        skiprows: int | list[int] | typing.Callable[[int], bool] | None = None,
+        skipfooter: int = 0,
+        skip_blank_rows: bool = False,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+        values: typing.Literal["typed", "formatted"] = "typed",
+        floats: typing.Literal["float", "decimal"] = "float",
+        numbers: typing.Literal["keep", "int", "float"] = "keep",
     ) -> typing.Iterator[
         list[
             int
             | float
+            | decimal.Decimal
             | str
             | bool
             | datetime.time
             | datetime.date
             | datetime.datetime
             | datetime.timedelta
+            | CellError
+            | typing.Any
         ]
     ]:
-        """Retunrning data from sheet as iterator of lists."""
+        """Retunrning data from sheet as iterator of lists.
+
+        Args:
+            errors (str): How to return error cells, see `to_python`.
+            empty_value (Any): Value for empty cells, see `to_python`.
+            fill_merged (bool): Fill merged regions, see `to_python`.
+            usecols (str | list[int] | list[str] | None): Columns to return, see `to_python`.
+            skiprows (int | list[int] | Callable[[int], bool] | None):
+                Rows to skip, see `to_python`, indexes are counted from the top of sheet.
+            skipfooter (int): Number of rows to skip at the bottom.
+            skip_blank_rows (bool): Skip blank rows, see `to_python`.
+            dates (str | None): How to return dates, see `to_python`.
+            values (str): Cells as Python objects or formatted text, see `to_python`.
+            floats (str): Float cells as `float` or `decimal.Decimal`, see `to_python`.
+            numbers (str): Numbers as stored, whole numbers as `int` or all as `float`,
+                see `to_python`.
+        """
+
+@typing.final
+class CalamineTable:
+    name: str
+    sheet_name: str
+    columns: list[str]
+    """Names of columns from the header of table."""
+    data: CalamineSheet
+    """Data of table without header, positions of cells are the same as in sheet."""
 
 @typing.final
 class CalamineWorkbook(contextlib.AbstractContextManager):
     path: str | None
     sheet_names: list[str]
     sheets_metadata: list[SheetMetadata]
+    defined_names: list[tuple[str, str, str | None]]
+    """Defined names as (name, formula, scope) tuples.
+
+    Scope is the sheet name for sheet-scoped names, `None` for workbook-level names
+    and for all names of formats, where scopes aren't read (xls, xlsb, ods).
+    Read from workbook on first access."""
+    empty_value: typing.Any
+    """Default value for empty cells of sheets loaded after setting, `""` by default."""
+    dates: typing.Literal["infer", "raw", "datetime"]
+    """Default `dates` mode (see `CalamineSheet.to_python`) of sheets loaded after setting,
+    also used by `CalamineSheet.to_arrow`, `infer` by default."""
     @classmethod
     def from_object(
-        cls, path_or_filelike: str | os.PathLike | ReadBuffer
+        cls,
+        path_or_filelike: str | os.PathLike | ReadBuffer | bytes | bytearray | memoryview,
+        mmap: bool = False,
+        format: WorkbookFormat | None = None,
     ) -> "CalamineWorkbook":
         """Determining type of pyobject and reading from it.
 
         Args:
-            path_or_filelike (str | os.PathLike | ReadBuffer): path to file or IO (must imlpement read/seek methods).
+            path_or_filelike (str | os.PathLike | ReadBuffer | bytes | bytearray | memoryview):
+                path to file, IO (must imlpement read/seek methods)
+                or bytes-like object (read-only one without copying, see `from_bytes`).
+            mmap (bool): read file through memory map, used for paths only (see `from_path`).
+            format (WorkbookFormat | None): format of file, detected if not given (see `from_path`).
         """
 
     @classmethod
-    def from_path(cls, path: str | os.PathLike) -> "CalamineWorkbook":
+    def from_path(
+        cls,
+        path: str | os.PathLike,
+        mmap: bool = False,
+        format: WorkbookFormat | None = None,
+    ) -> "CalamineWorkbook":
         """Reading file from path.
 
         Args:
             path (str | os.PathLike): path to file.
+            mmap (bool): read file through read-only memory map instead of buffered reads,
+                the file must not be modified until workbook is closed. Truncating
+                the mapped file crashes the process with SIGBUS on the next read.
+            format (WorkbookFormat | None): format of file, `xlsm` is read like `xlsx`.
+                By default, it's detected by extension of path (by content of file
+                for unknown extensions), IO and bytes are detected by content.
         """
 
     @classmethod
-    def from_filelike(cls, filelike: ReadBuffer) -> "CalamineWorkbook":
+    def from_filelike(
+        cls,
+        filelike: ReadBuffer,
+        lazy: bool = False,
+        format: WorkbookFormat | None = None,
+    ) -> "CalamineWorkbook":
         """Reading file from IO.
 
         Args:
             filelike : IO (must imlpement read/seek methods).
+            lazy (bool): read only needed parts of file on demand instead of reading it whole.
+                The file must be opened in binary mode and stay open until workbook is closed,
+                it must not be used by other threads meanwhile (each read seeks the file
+                and restores its position).
+            format (WorkbookFormat | None): format of file, detected if not given (see `from_path`).
+        """
+
+    @classmethod
+    def from_bytes(
+        cls,
+        data: bytes | bytearray | memoryview,
+        format: WorkbookFormat | None = None,
+    ) -> "CalamineWorkbook":
+        """Reading file from bytes-like object (any C-contiguous buffer).
+
+        Read-only buffers (`bytes`, read-only `memoryview`) are read in place without copying,
+        the buffer is kept until workbook is closed. Mutable buffers (`bytearray`,
+        writable `memoryview`) are copied.
+
+        Args:
+            data (bytes | bytearray | memoryview): content of file.
+            format (WorkbookFormat | None): format of file, detected if not given (see `from_path`).
         """
 
     def close(self) -> None:
@@ -137,11 +582,26 @@ class CalamineWorkbook(contextlib.AbstractContextManager):
         exc_val: BaseException | None,
         exc_tb: types.TracebackType | None,
     ) -> None: ...
-    def get_sheet_by_name(self, name: str) -> CalamineSheet:
+    def get_sheet_by_name(
+        self,
+        name: str,
+        formulas: bool = False,
+        merged_cells: bool = False,
+        nrows: int | None = None,
+        skiprows: int = 0,
+        number_formats: bool = False,
+    ) -> CalamineSheet:
         """Get worksheet by name.
 
         Args:
             name(str): name of worksheet
+            formulas(bool): load formulas for `CalamineSheet.formulas`
+            merged_cells(bool): load merged regions for `CalamineSheet.merged_cells`
+            nrows(int | None): load only this number of rows after `skiprows`,
+                xlsx and xlsb files are not parsed further
+            skiprows(int): skip this number of rows from the top of the sheet
+            number_formats(bool): load number formats for `CalamineSheet.number_formats`
+                and `to_python(values="formatted")`, supported only for xlsx
 
         Returns:
             CalamineSheet
@@ -151,15 +611,127 @@ class CalamineWorkbook(contextlib.AbstractContextManager):
             WorksheetNotFound: If worksheet not found in workbook.
         """
 
-    def get_sheet_by_index(self, index: int) -> CalamineSheet:
+    def get_sheet_by_index(
+        self,
+        index: int,
+        formulas: bool = False,
+        merged_cells: bool = False,
+        nrows: int | None = None,
+        skiprows: int = 0,
+        number_formats: bool = False,
+    ) -> CalamineSheet:
         """Get worksheet by index.
 
         Args:
             index(int): index of worksheet
+            formulas(bool): load formulas for `CalamineSheet.formulas`
+            merged_cells(bool): load merged regions for `CalamineSheet.merged_cells`
+            nrows(int | None): load only this number of rows after `skiprows`,
+                xlsx and xlsb files are not parsed further
+            skiprows(int): skip this number of rows from the top of the sheet
+            number_formats(bool): load number formats for `CalamineSheet.number_formats`
+                and `to_python(values="formatted")`, supported only for xlsx
+
+        Returns:
+            CalamineSheet
+
+        Raises:
+            WorkbookClosed: If workbook already closed.
+            WorksheetNotFound: If worksheet not found in workbook.
+        """
+
+    def get_named_range(self, name: str) -> CalamineSheet:
+        """Get cells of defined name, which refers to one area like `Sheet1!$A$1:$C$10`.
+
+        Workbook-level name is preferred over sheet-scoped names with the same name
+        (for xls, xlsb and ods, which don't store scopes, the first one is used).
+        Whole rows and columns (`Sheet1!$A:$C`) are limited by data of sheet,
+        cells of explicit bounds outside of data are empty.
+
+        Args:
+            name(str): defined name
 
         Returns:
             CalamineSheet
 
+        Raises:
+            CalamineError: If name not found or it isn't a reference to cells.
+            WorkbookClosed: If workbook already closed.
+            WorksheetNotFound: If worksheet not found in workbook.
+        """
+
+    @property
+    def table_names(self) -> list[str]:
+        """Names of all tables in workbook.
+
+        Raises:
+            CalamineError: If format isn't xlsx.
+            WorkbookClosed: If workbook already closed.
+        """
+
+    def table_names_in_sheet(self, sheet_name: str) -> list[str]:
+        """Names of tables in worksheet.
+
+        Args:
+            sheet_name(str): name of worksheet
+
+        Raises:
+            CalamineError: If format isn't xlsx.
+            WorkbookClosed: If workbook already closed.
+        """
+
+    def get_table(self, name: str) -> CalamineTable:
+        """Get table (ListObject) by name.
+
+        Args:
+            name(str): name of table
+
+        Returns:
+            CalamineTable
+
+        Raises:
+            CalamineError: If format isn't xlsx or table not found.
+            WorkbookClosed: If workbook already closed.
+        """
+
+    def iter_sheet_rows(
+        self,
+        name: str,
+        errors: typing.Literal["value", "none", "string", "raise"] = "value",
+        empty_value: typing.Any = ...,
+        dates: typing.Literal["infer", "raw", "datetime"] | None = None,
+        usecols: str | list[int] | list[str] | None = None,
+    ) -> typing.Iterator[
+        list[
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | CellError
+            | typing.Any
+        ]
+    ]:
+        """Iterate over rows of worksheet without loading the whole sheet into memory.
+
+        Rows start from the cell A1, like `to_python(skip_empty_area=False)`.
+        Streaming is supported for xlsx and xlsb, other formats load the sheet first.
+        Rows are padded to the sheet dimension stored in the file. Cells outside of it
+        (or of sheets without it) widen their row and all the next ones, so the rows
+        before them are narrower.
+
+        Args:
+            name(str): name of worksheet
+            errors (str): How to return error cells, see `CalamineSheet.to_python`.
+            empty_value (Any): Value for empty cells, `empty_value` of workbook by default.
+            dates (str | None): How to return dates, `dates` of workbook by default.
+            usecols (str | list[int] | list[str] | None):
+                Columns to return, see `CalamineSheet.to_python`. Positions are counted
+                from column A and names are taken from the first row of sheet.
+
         Raises:
             WorkbookClosed: If workbook already closed.
             WorksheetNotFound: If worksheet not found in workbook.
@@ -171,12 +743,34 @@ class WorksheetNotFound(CalamineError): ...
 class XmlError(CalamineError): ...
 class ZipError(CalamineError): ...
 class WorkbookClosed(CalamineError): ...
+class CellValueError(CalamineError): ...
 
 def load_workbook(
-    path_or_filelike: str | os.PathLike | ReadBuffer,
+    path_or_filelike: str | os.PathLike | ReadBuffer | bytes | bytearray | memoryview,
+    mmap: bool = False,
+    format: WorkbookFormat | None = None,
 ) -> CalamineWorkbook:
     """Determining type of pyobject and reading from it.
 
     Args:
-        path_or_filelike (str | os.PathLike | ReadBuffer): path to file or IO (must imlpement read/seek methods).
+        path_or_filelike (str | os.PathLike | ReadBuffer | bytes | bytearray | memoryview):
+            path to file, IO (must imlpement read/seek methods)
+            or bytes-like object (read-only one without copying,
+            see `CalamineWorkbook.from_bytes`).
+        mmap (bool): read file through memory map, used for paths only
+            (see `CalamineWorkbook.from_path`).
+        format (WorkbookFormat | None): format of file, detected if not given
+            (see `CalamineWorkbook.from_path`).
+    """
+
+def detect_format(
+    path_or_bytes: str | os.PathLike | bytes | bytearray | memoryview,
+) -> DetectedFormat:
+    """Detecting format of file by magic bytes. Zip packages are checked by
+    the readers of formats tried in order, CFB files by their directory,
+    sheets are not read.
+
+    Args:
+        path_or_bytes (str | os.PathLike | bytes | bytearray | memoryview):
+            path to file or content of file.
     """
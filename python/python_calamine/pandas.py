@@ -21,7 +21,7 @@ if TYPE_CHECKING:
     from pandas._typing import FilePath, ReadBuffer, StorageOptions
     from python_calamine import CalamineSheet, CalamineWorkbook
 
-_CellValueT = Union[int, float, str, bool, time, date, datetime, timedelta]
+_CellValueT = Union[int, float, str, bool, time, date, datetime, timedelta, None]
 
 
 PANDAS_VERSION = parse(version("pandas"))
@@ -118,7 +118,11 @@ class CalamineExcelReader(BaseExcelReader):
 
             return value
 
-        rows: list[list[_CellValueT]] = sheet.to_python(skip_empty_area=False)
+        # errors="none" guarantees that there is no CellError in rows
+        rows = cast(
+            "list[list[_CellValueT]]",
+            sheet.to_python(skip_empty_area=False, errors="none"),
+        )
         data: list[list[Scalar]] = []
 
         for row in rows:
@@ -0,0 +1,286 @@
+//! Detection of workbook format by magic bytes. Zip packages are opened by calamine readers
+//! tried in order, like `open_workbook_auto`. CFB and BIFF files are checked by a small probe
+//! of directory and BOF record instead: calamine doesn't report BIFF version or encryption,
+//! and its CFB reader doesn't stop on looped sector chains of broken files.
+use std::collections::HashSet;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use zip::ZipArchive;
+
+use crate::types::WorkbookFormat;
+
+const CFB_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+/// Sector numbers above are special values (end of chain, free, FAT or DIFAT sector)
+const MAX_REGULAR_SECTOR: u32 = 0xFFFF_FFFA;
+const DIR_ENTRY_SIZE: usize = 128;
+/// Streams of xlsx, xlsm and xlsb encrypted with password [MS-OFFCRYPTO]
+const ENCRYPTION_STREAMS: [&str; 2] = ["EncryptionInfo", "EncryptedPackage"];
+/// Limit of `[Content_Types].xml` read for checking macro-enabled workbook
+const MAX_CONTENT_TYPES_LEN: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Format of workbook, the same as given explicitly for opening it,
+    /// with BIFF version (2, 3, 4, 5 or 8) of Excel 97-2003 files
+    Workbook(WorkbookFormat, Option<u8>),
+    /// Password protected xlsx, xlsm or xlsb (OOXML package encrypted inside CFB)
+    Encrypted,
+    Unknown,
+}
+
+/// `reader` returns new reader from the start of file.
+pub fn detect_format<RS: Read + Seek>(
+    mut reader: impl FnMut() -> io::Result<RS>,
+) -> io::Result<Format> {
+    let mut magic = Vec::with_capacity(CFB_SIGNATURE.len());
+    reader()?
+        .take(CFB_SIGNATURE.len() as u64)
+        .read_to_end(&mut magic)?;
+
+    if magic.starts_with(b"PK\x03\x04") || magic.starts_with(b"PK\x05\x06") {
+        detect_zip(reader)
+    } else if magic == CFB_SIGNATURE {
+        match detect_cfb(reader()?) {
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                Ok(Format::Unknown)
+            }
+            format => format,
+        }
+    } else {
+        // BIFF2-4 files are not wrapped into CFB and start with BOF record
+        Ok(xls_format(&magic))
+    }
+}
+
+fn detect_zip<RS: Read + Seek>(mut reader: impl FnMut() -> io::Result<RS>) -> io::Result<Format> {
+    for format in [
+        WorkbookFormat::Xlsx,
+        WorkbookFormat::Xlsb,
+        WorkbookFormat::Ods,
+    ] {
+        if format.open(reader()?).is_err() {
+            continue;
+        }
+        let format = match format {
+            WorkbookFormat::Xlsx if is_macro_enabled(reader()?) => WorkbookFormat::Xlsm,
+            format => format,
+        };
+        return Ok(Format::Workbook(format, None));
+    }
+    Ok(Format::Unknown)
+}
+
+/// xlsm differs from xlsx only by content type of workbook part.
+fn is_macro_enabled<RS: Read + Seek>(reader: RS) -> bool {
+    let Ok(mut zip) = ZipArchive::new(reader) else {
+        return false;
+    };
+    let mut content_types = String::new();
+    zip.by_name("[Content_Types].xml")
+        .map(|file| {
+            file.take(MAX_CONTENT_TYPES_LEN)
+                .read_to_string(&mut content_types)
+                .is_ok()
+        })
+        .unwrap_or(false)
+        && content_types.contains("macroEnabled.main+xml")
+}
+
+/// BIFF version from BOF record at the start of workbook stream, like calamine `parse_bof`.
+fn bof_version(data: &[u8]) -> Option<u8> {
+    let read_u16 = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    match read_u16(0)? {
+        0x0009 => Some(2),
+        0x0209 => Some(3),
+        0x0409 => Some(4),
+        0x0809 => match (read_u16(4)?, read_u16(6)) {
+            (0x0200 | 0x0002 | 0x0007, _) => Some(2),
+            (0x0300, _) => Some(3),
+            (0x0400, _) => Some(4),
+            (0x0500, _) | (0, Some(0x1000)) => Some(5),
+            _ => Some(8),
+        },
+        _ => None,
+    }
+}
+
+fn xls_format(data: &[u8]) -> Format {
+    bof_version(data).map_or(Format::Unknown, |version| {
+        Format::Workbook(WorkbookFormat::Xls, Some(version))
+    })
+}
+
+fn detect_cfb<RS: Read + Seek>(reader: RS) -> io::Result<Format> {
+    let mut cfb = Cfb::new(reader)?;
+    let entries = cfb.directory()?;
+    let find = |name: &str| entries.iter().find(|e| e.name == name);
+
+    if ENCRYPTION_STREAMS.iter().all(|name| find(name).is_some()) {
+        return Ok(Format::Encrypted);
+    }
+    let Some(workbook) = find("Workbook").or_else(|| find("Book")) else {
+        return Ok(Format::Unknown);
+    };
+    let root = &entries[0];
+    let bof = cfb.stream_start(workbook, root)?;
+    Ok(xls_format(&bof))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn u32_at(data: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]])
+}
+
+struct DirEntry {
+    name: String,
+    start: u32,
+    size: u64,
+}
+
+/// Compound File Binary [MS-CFB], reads only FAT, directory and the start of streams.
+struct Cfb<RS> {
+    reader: RS,
+    sector_size: usize,
+    mini_cutoff: u64,
+    first_dir_sector: u32,
+    fat: Vec<u32>,
+}
+
+impl<RS: Read + Seek> Cfb<RS> {
+    fn new(mut reader: RS) -> io::Result<Self> {
+        let mut header = [0u8; 512];
+        reader.read_exact(&mut header)?;
+        let sector_size = match u16::from_le_bytes([header[0x1E], header[0x1F]]) {
+            9 => 512,
+            12 => 4096,
+            _ => return Err(invalid("invalid sector size")),
+        };
+        let mut cfb = Cfb {
+            reader,
+            sector_size,
+            mini_cutoff: u64::from(u32_at(&header, 0x38)),
+            first_dir_sector: u32_at(&header, 0x30),
+            fat: Vec::new(),
+        };
+
+        // the first 109 FAT sectors are listed in header, others in DIFAT chain,
+        // the count of broken files is limited by sectors in file
+        let file_len = cfb.reader.seek(SeekFrom::End(0))?;
+        let fat_sectors_count =
+            (u32_at(&header, 0x2C) as usize).min((file_len / sector_size as u64) as usize);
+        let mut fat_sectors: Vec<u32> = (0..109).map(|i| u32_at(&header, 0x4C + i * 4)).collect();
+        let mut difat_sector = u32_at(&header, 0x44);
+        let mut difat_sectors = HashSet::new();
+        while difat_sector <= MAX_REGULAR_SECTOR && fat_sectors.len() < fat_sectors_count {
+            if !difat_sectors.insert(difat_sector) {
+                return Err(invalid("looped DIFAT chain"));
+            }
+            let sector = cfb.read_sector(difat_sector)?;
+            let last = sector_size - 4;
+            fat_sectors.extend((0..last).step_by(4).map(|i| u32_at(&sector, i)));
+            difat_sector = u32_at(&sector, last);
+        }
+        fat_sectors.truncate(fat_sectors_count);
+        for fat_sector in fat_sectors {
+            let sector = cfb.read_sector(fat_sector)?;
+            cfb.fat
+                .extend((0..sector_size).step_by(4).map(|i| u32_at(&sector, i)));
+        }
+        Ok(cfb)
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.reader.read_exact(buf)
+    }
+
+    fn read_sector(&mut self, sector: u32) -> io::Result<Vec<u8>> {
+        if sector > MAX_REGULAR_SECTOR {
+            return Err(invalid("invalid sector"));
+        }
+        let mut buf = vec![0u8; self.sector_size];
+        let offset = (u64::from(sector) + 1) * self.sector_size as u64;
+        self.read_at(offset, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Up to `limit` sectors of chain starting at `sector`. Every sector is in FAT and
+    /// visited once, so the walk ends within FAT length for looped chains of broken files.
+    fn chain(&self, mut sector: u32, limit: usize) -> io::Result<Vec<u32>> {
+        let mut sectors = Vec::new();
+        let mut visited = HashSet::new();
+        while sector <= MAX_REGULAR_SECTOR && sectors.len() < limit {
+            if !visited.insert(sector) {
+                return Err(invalid("looped sector chain"));
+            }
+            sectors.push(sector);
+            sector = *self
+                .fat
+                .get(sector as usize)
+                .ok_or_else(|| invalid("invalid sector chain"))?;
+        }
+        Ok(sectors)
+    }
+
+    /// Entries of all storages and streams, the first one is the root entry.
+    fn directory(&mut self) -> io::Result<Vec<DirEntry>> {
+        let mut entries = Vec::new();
+        for sector in self.chain(self.first_dir_sector, usize::MAX)? {
+            let data = self.read_sector(sector)?;
+            for entry in data.chunks_exact(DIR_ENTRY_SIZE) {
+                let name_len = (u16::from_le_bytes([entry[64], entry[65]]) as usize).min(64);
+                let name: Vec<u16> = entry[..name_len.saturating_sub(2)]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                let mut size = u64::from_le_bytes(entry[120..128].try_into().unwrap());
+                if self.sector_size == 512 {
+                    // the high part may be garbage in version 3
+                    size &= 0xFFFF_FFFF;
+                }
+                entries.push(DirEntry {
+                    name: String::from_utf16_lossy(&name),
+                    start: u32_at(entry, 116),
+                    size,
+                });
+            }
+        }
+        if entries.is_empty() {
+            return Err(invalid("empty directory"));
+        }
+        Ok(entries)
+    }
+
+    /// The first bytes of stream (enough for BOF record).
+    fn stream_start(&mut self, entry: &DirEntry, root: &DirEntry) -> io::Result<Vec<u8>> {
+        const LEN: usize = 8;
+        let mut buf = vec![0u8; LEN.min(entry.size as usize)];
+        if entry.size >= self.mini_cutoff {
+            let offset = (u64::from(entry.start) + 1) * self.sector_size as u64;
+            self.read_at(offset, &mut buf)?;
+            return Ok(buf);
+        }
+
+        // small streams are stored in 64 bytes mini sectors inside of root entry stream,
+        // the first mini sector of stream is enough, so mini FAT isn't read
+        let sector_size = self.sector_size as u64;
+        let mini_offset = u64::from(entry.start) * 64;
+        let index = usize::try_from(mini_offset / sector_size)
+            .map_err(|_| invalid("invalid mini sector"))?;
+        let sector = *self
+            .chain(root.start, index.saturating_add(1))?
+            .get(index)
+            .ok_or_else(|| invalid("invalid mini sector"))?;
+        let offset = (u64::from(sector) + 1) * sector_size + mini_offset % sector_size;
+        self.read_at(offset, &mut buf)?;
+        Ok(buf)
+    }
+}
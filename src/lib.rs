@@ -1,25 +1,50 @@
 use pyo3::prelude::*;
 
+mod detect;
+mod numfmt;
+mod ods;
 mod types;
 mod utils;
+mod xlsb;
+mod xlsx;
 use crate::types::{
-    CalamineError, CalamineSheet, CalamineWorkbook, CellValue, Error, PasswordError, SheetMetadata,
-    SheetTypeEnum, SheetVisibleEnum, WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
+    CalamineArrowTable, CalamineError, CalamineSheet, CalamineTable, CalamineWorkbook, CellError,
+    CellErrorTypeEnum, CellValue, CellValueError, ConvertOptions, DatesMode, DetectedFormat,
+    EmptyValueArg, Error, ErrorsMode, FloatsMode, NumbersMode, PasswordError, SheetMetadata,
+    SheetTypeEnum, SheetVisibleEnum, ValuesMode, WorkbookClosed, WorkbookFormat, WorksheetNotFound,
+    XmlError, ZipError,
 };
 
 #[pyfunction]
-fn load_workbook(py: Python, path_or_filelike: PyObject) -> PyResult<CalamineWorkbook> {
-    CalamineWorkbook::from_object(py, path_or_filelike)
+#[pyo3(signature = (path_or_filelike, mmap=false, format=None))]
+fn load_workbook(
+    py: Python,
+    path_or_filelike: PyObject,
+    mmap: bool,
+    format: Option<WorkbookFormat>,
+) -> PyResult<CalamineWorkbook> {
+    CalamineWorkbook::from_object(py, path_or_filelike, mmap, format)
+}
+
+#[pyfunction]
+fn detect_format(py: Python, path_or_bytes: &Bound<'_, PyAny>) -> PyResult<DetectedFormat> {
+    DetectedFormat::from_object(py, path_or_bytes)
 }
 
 #[pymodule]
 fn _python_calamine(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(load_workbook, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_format, m)?)?;
     m.add_class::<CalamineWorkbook>()?;
     m.add_class::<CalamineSheet>()?;
+    m.add_class::<CalamineTable>()?;
     m.add_class::<SheetMetadata>()?;
     m.add_class::<SheetTypeEnum>()?;
     m.add_class::<SheetVisibleEnum>()?;
+    m.add_class::<CellError>()?;
+    m.add_class::<CellErrorTypeEnum>()?;
+    m.add_class::<CalamineArrowTable>()?;
+    m.add_class::<DetectedFormat>()?;
     m.add("CalamineError", py.get_type_bound::<CalamineError>())?;
     m.add("PasswordError", py.get_type_bound::<PasswordError>())?;
     m.add(
@@ -29,5 +54,6 @@ fn _python_calamine(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("XmlError", py.get_type_bound::<XmlError>())?;
     m.add("ZipError", py.get_type_bound::<ZipError>())?;
     m.add("WorkbookClosed", py.get_type_bound::<WorkbookClosed>())?;
+    m.add("CellValueError", py.get_type_bound::<CellValueError>())?;
     Ok(())
 }
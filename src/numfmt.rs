@@ -0,0 +1,850 @@
+//! Rendering of cell values with Excel number format codes, like Excel displays them.
+//!
+//! Supported: sections (`positive;negative;zero;text`), digit placeholders `0 # ?`,
+//! thousands separators and scaling, percents, scientific notation, fractions (`# ?/?`),
+//! conditional sections (`[>100]`), literals (quoted, escaped, `_x` paddings,
+//! `[$€-407]` currencies), dates and times (including elapsed `[h]`, `AM/PM` and
+//! fractional seconds) and the text placeholder `@`.
+//! Colors and fill characters (`*x`) are ignored. Names of months and days are English.
+use calamine::{Data, ExcelDateTime, ExcelDateTimeType};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    /// `0`, `#` or `?`
+    Digit(char),
+    Point,
+    Comma,
+    Percent,
+    /// `E+` (true) or `E-` (false)
+    Exp(bool),
+    /// `@`
+    Text,
+    General,
+    /// `yyyy`, `mm`, `h`, `[h]`, `AM/PM`, etc. in lowercase (except `AM/PM` and `A/P`)
+    Date(String),
+}
+
+/// Splits format code into sections by `;`, skipping quoted and escaped characters.
+fn split_sections(code: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in code.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if !quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                sections.push(&code[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    sections.push(&code[start..]);
+    sections
+}
+
+/// Condition of section like `[>=100]`.
+struct Condition<'a> {
+    operator: &'a str,
+    value: f64,
+}
+
+impl Condition<'_> {
+    fn matches(&self, value: f64) -> bool {
+        match self.operator {
+            "<" => value < self.value,
+            "<=" | "=<" => value <= self.value,
+            ">" => value > self.value,
+            ">=" | "=>" => value >= self.value,
+            "=" => value == self.value,
+            "<>" => value != self.value,
+            _ => false,
+        }
+    }
+}
+
+/// Returns condition of section, skipping quoted and escaped characters.
+fn section_condition(section: &str) -> Option<Condition<'_>> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in section.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if !quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '[' if !quoted => {
+                let content = &section[i + 1..];
+                let content = &content[..content.find(']').unwrap_or(content.len())];
+                let split = content
+                    .find(|c| !matches!(c, '<' | '>' | '='))
+                    .unwrap_or(content.len());
+                if split > 0 {
+                    let (operator, value) = content.split_at(split);
+                    return value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .map(|value| Condition { operator, value });
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+fn tokenize(section: &str) -> Vec<Token> {
+    let chars: Vec<char> = section.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let lower = c.to_ascii_lowercase();
+        match c {
+            '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '"')
+                    .map_or(chars.len(), |end| i + 1 + end);
+                tokens.push(Token::Literal(chars[i + 1..end].iter().collect()));
+                i = end + 1;
+                continue;
+            }
+            '\\' => {
+                if let Some(&next) = chars.get(i + 1) {
+                    tokens.push(Token::Literal(next.to_string()));
+                }
+                i += 2;
+                continue;
+            }
+            '_' => {
+                tokens.push(Token::Literal(" ".to_string()));
+                i += 2;
+                continue;
+            }
+            '*' => {
+                i += 2;
+                continue;
+            }
+            '[' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == ']')
+                    .map_or(chars.len(), |end| i + 1 + end);
+                let content: String = chars[i + 1..end].iter().collect();
+                let lower_content = content.to_ascii_lowercase();
+                if let Some(currency) = content.strip_prefix('$') {
+                    let symbol = currency.split('-').next().unwrap_or_default();
+                    tokens.push(Token::Literal(symbol.to_string()));
+                } else if let Some(unit @ ('h' | 'm' | 's')) = lower_content
+                    .chars()
+                    .next()
+                    .filter(|&unit| lower_content.chars().all(|c| c == unit))
+                {
+                    tokens.push(Token::Date(format!("[{}]", unit)));
+                }
+                // colors, locales and conditions (see `section_condition`) are ignored
+                i = end + 1;
+                continue;
+            }
+            '0' | '#' | '?' => tokens.push(Token::Digit(c)),
+            '.' => tokens.push(Token::Point),
+            ',' => tokens.push(Token::Comma),
+            '%' => tokens.push(Token::Percent),
+            '@' => tokens.push(Token::Text),
+            'E' | 'e' if matches!(chars.get(i + 1), Some('+' | '-')) => {
+                tokens.push(Token::Exp(chars[i + 1] == '+'));
+                i += 2;
+                continue;
+            }
+            'A' | 'a' => {
+                let rest: String = chars[i..].iter().take(5).collect();
+                if rest.eq_ignore_ascii_case("AM/PM") {
+                    tokens.push(Token::Date("AM/PM".to_string()));
+                    i += 5;
+                    continue;
+                }
+                let rest: String = chars[i..].iter().take(3).collect();
+                if rest.eq_ignore_ascii_case("A/P") {
+                    tokens.push(Token::Date("A/P".to_string()));
+                    i += 3;
+                    continue;
+                }
+                tokens.push(Token::Literal(c.to_string()));
+            }
+            'G' | 'g'
+                if chars[i..]
+                    .iter()
+                    .take(7)
+                    .collect::<String>()
+                    .eq_ignore_ascii_case("general") =>
+            {
+                tokens.push(Token::General);
+                i += 7;
+                continue;
+            }
+            _ if matches!(lower, 'y' | 'm' | 'd' | 'h' | 's') => {
+                let count = chars[i..]
+                    .iter()
+                    .take_while(|c| c.to_ascii_lowercase() == lower)
+                    .count();
+                tokens.push(Token::Date(lower.to_string().repeat(count)));
+                i += count;
+                continue;
+            }
+            _ => tokens.push(Token::Literal(c.to_string())),
+        }
+        i += 1;
+    }
+    tokens
+}
+
+/// Formats number like Excel's `General` format: up to 10 significant digits,
+/// scientific notation for very large and small numbers.
+pub fn format_general(value: f64) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value.fract() == 0.0 && value.abs() < 1e11 {
+        return format!("{}", value as i64);
+    }
+    let exponent = value.abs().log10().floor() as i32;
+    if (-9..11).contains(&exponent) {
+        let decimals = (9 - exponent).max(0) as usize;
+        let text = format!("{:.*}", decimals, value);
+        let text = if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &text
+        };
+        text.to_string()
+    } else {
+        let text = format!("{:.5E}", value);
+        let (mantissa, exponent) = text.split_once('E').unwrap_or((&text, "0"));
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        let exponent: i32 = exponent.parse().unwrap_or_default();
+        format!(
+            "{}E{}{:02}",
+            mantissa,
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+    }
+}
+
+/// Converts Excel serial number (1900 date system) to datetime, rounded to `precision`
+/// (number of digits of fractional seconds).
+fn serial_to_datetime(value: f64, precision: u32) -> Option<NaiveDateTime> {
+    if value < 0.0 {
+        return None;
+    }
+    // Excel treats 1900 as a leap year, serials before 1900-03-01 are shifted by one day
+    let epoch = if value < 60.0 {
+        NaiveDate::from_ymd_opt(1899, 12, 31)?
+    } else {
+        NaiveDate::from_ymd_opt(1899, 12, 30)?
+    };
+    let scale = 10f64.powi(precision as i32);
+    let units = (value * 86400.0 * scale).round() as i64;
+    let nanos = units.checked_mul(1_000_000_000 / scale as i64)?;
+    epoch
+        .and_hms_opt(0, 0, 0)?
+        .checked_add_signed(Duration::nanoseconds(nanos))
+}
+
+/// Renders serial `value` with date tokens, `offset` days are added to the date parts to
+/// convert serials of 1904 date system.
+fn format_date(value: f64, offset: f64, tokens: &[Token]) -> String {
+    // digits after point in seconds, like `ss.000`, up to nanoseconds
+    let precision = tokens
+        .iter()
+        .skip_while(|token| **token != Token::Point)
+        .skip(1)
+        .take_while(|token| **token == Token::Digit('0'))
+        .count()
+        .min(9) as u32;
+    // negative serials are not dates in either date system
+    let dt = if value < 0.0 {
+        None
+    } else {
+        serial_to_datetime(value + offset, precision)
+    };
+    let Some(dt) = dt else {
+        return format_general(value);
+    };
+    let twelve_hours = tokens
+        .iter()
+        .any(|token| matches!(token, Token::Date(d) if d == "AM/PM" || d == "A/P"));
+    let total_seconds =
+        (value * 86400.0 * 10f64.powi(precision as i32)).round() / 10f64.powi(precision as i32);
+
+    let date_tokens: Vec<(usize, &str)> = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, token)| match token {
+            Token::Date(d) => Some((i, d.as_str())),
+            _ => None,
+        })
+        .collect();
+    // `m` is minutes after hours or before seconds
+    let is_minutes = |index: usize| {
+        let position = date_tokens.iter().position(|&(i, _)| i == index).unwrap();
+        let after_hours = position > 0
+            && (date_tokens[position - 1].1.starts_with('h')
+                || date_tokens[position - 1].1 == "[h]");
+        let before_seconds = date_tokens
+            .get(position + 1)
+            .is_some_and(|&(_, d)| d.starts_with('s') || d == "[s]");
+        after_hours || before_seconds
+    };
+
+    let mut result = String::new();
+    let mut in_fraction = false;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Date(d) => {
+                in_fraction = false;
+                let hour = if twelve_hours {
+                    match dt.hour() % 12 {
+                        0 => 12,
+                        hour => hour,
+                    }
+                } else {
+                    dt.hour()
+                };
+                let text = match d.as_str() {
+                    "y" | "yy" => format!("{:02}", dt.year() % 100),
+                    d if d.starts_with('y') => format!("{:04}", dt.year()),
+                    "m" | "mm" if is_minutes(i) => {
+                        if d == "m" {
+                            dt.minute().to_string()
+                        } else {
+                            format!("{:02}", dt.minute())
+                        }
+                    }
+                    "m" => dt.month().to_string(),
+                    "mm" => format!("{:02}", dt.month()),
+                    "mmm" => MONTHS[dt.month0() as usize][..3].to_string(),
+                    "mmmmm" => MONTHS[dt.month0() as usize][..1].to_string(),
+                    d if d.starts_with('m') => MONTHS[dt.month0() as usize].to_string(),
+                    "d" => dt.day().to_string(),
+                    "dd" => format!("{:02}", dt.day()),
+                    "ddd" => DAYS[dt.weekday().num_days_from_monday() as usize][..3].to_string(),
+                    d if d.starts_with('d') => {
+                        DAYS[dt.weekday().num_days_from_monday() as usize].to_string()
+                    }
+                    "h" => hour.to_string(),
+                    d if d.starts_with('h') => format!("{:02}", hour),
+                    "s" => dt.second().to_string(),
+                    d if d.starts_with('s') => format!("{:02}", dt.second()),
+                    "[h]" => ((total_seconds / 3600.0).floor() as i64).to_string(),
+                    "[m]" => ((total_seconds / 60.0).floor() as i64).to_string(),
+                    "[s]" => (total_seconds.floor() as i64).to_string(),
+                    "AM/PM" => if dt.hour() < 12 { "AM" } else { "PM" }.to_string(),
+                    "A/P" => if dt.hour() < 12 { "A" } else { "P" }.to_string(),
+                    _ => String::new(),
+                };
+                result.push_str(&text);
+            }
+            Token::Point if precision > 0 => {
+                in_fraction = true;
+                let fraction = format!("{:09}", dt.nanosecond() % 1_000_000_000);
+                result.push('.');
+                result.push_str(&fraction[..precision as usize]);
+            }
+            Token::Digit(_) if in_fraction => (),
+            Token::Literal(text) => result.push_str(text),
+            Token::Point => result.push('.'),
+            Token::Comma => result.push(','),
+            Token::Percent => result.push('%'),
+            Token::Digit(c) => result.push(*c),
+            _ => (),
+        }
+    }
+    result
+}
+
+/// Fills integer placeholders from right to left, extra digits go to the leftmost one.
+fn fill_integer(digits: &str, placeholders: &[char]) -> Vec<String> {
+    let mut digits: Vec<char> = digits.chars().collect();
+    let mut filled = vec![String::new(); placeholders.len()];
+    for (i, &placeholder) in placeholders.iter().enumerate().rev() {
+        filled[i] = match digits.pop() {
+            Some(digit) => digit.to_string(),
+            None => match placeholder {
+                '0' => "0".to_string(),
+                '?' => " ".to_string(),
+                _ => String::new(),
+            },
+        };
+    }
+    if let Some(first) = filled.first_mut() {
+        let rest: String = digits.into_iter().collect();
+        first.insert_str(0, &rest);
+    }
+    filled
+}
+
+fn group_thousands(digits: &str) -> String {
+    let offset = digits.len() % 3;
+    let mut result = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && i % 3 == offset {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn format_number(value: f64, tokens: &[Token], minus: bool) -> String {
+    let mut value = value;
+    let exp_index = tokens.iter().position(|t| matches!(t, Token::Exp(_)));
+    let number_end = exp_index.unwrap_or(tokens.len());
+    let point = tokens[..number_end].iter().position(|t| *t == Token::Point);
+    let int_end = point.unwrap_or(number_end);
+
+    let is_digit = |t: &Token| matches!(t, Token::Digit(_));
+    let int_placeholders: Vec<char> = tokens[..int_end]
+        .iter()
+        .filter_map(|t| match t {
+            Token::Digit(c) => Some(*c),
+            _ => None,
+        })
+        .collect();
+    let first_digit = tokens[..int_end].iter().position(is_digit);
+    let last_digit = tokens[..int_end].iter().rposition(is_digit);
+    let grouping = match (first_digit, last_digit) {
+        (Some(first), Some(last)) => tokens[first..last].contains(&Token::Comma),
+        _ => false,
+    };
+    // commas right after the last digit placeholder divide by 1000
+    if let Some(last) = tokens[..number_end].iter().rposition(is_digit) {
+        let scaling = tokens[last + 1..]
+            .iter()
+            .take_while(|t| **t == Token::Comma)
+            .count();
+        value /= 1000f64.powi(scaling as i32);
+    }
+    let percents = tokens.iter().filter(|t| **t == Token::Percent).count();
+    value *= 100f64.powi(percents as i32);
+
+    let decimal_placeholders: Vec<char> = match point {
+        Some(point) => tokens[point + 1..number_end]
+            .iter()
+            .filter_map(|t| match t {
+                Token::Digit(c) => Some(*c),
+                _ => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut exponent = 0i32;
+    if exp_index.is_some() && value != 0.0 {
+        let step = if int_placeholders.contains(&'#') {
+            int_placeholders.len().max(1) as i32
+        } else {
+            1
+        };
+        exponent = value.abs().log10().floor() as i32;
+        exponent -= exponent.rem_euclid(step);
+        if step == 1 {
+            exponent -= int_placeholders.len().max(1) as i32 - 1;
+        }
+        value /= 10f64.powi(exponent);
+        // rounding may produce 10.0
+        let rounded = format!("{:.*}", decimal_placeholders.len(), value);
+        if rounded.parse::<f64>().unwrap_or(value)
+            >= 10f64.powi(int_placeholders.len().max(1) as i32)
+        {
+            value /= 10.0;
+            exponent += 1;
+        }
+    }
+
+    let text = format!("{:.*}", decimal_placeholders.len(), value);
+    let (int_digits, frac_digits) = text.split_once('.').unwrap_or((&text, ""));
+    let int_digits = int_digits.trim_start_matches('0');
+
+    let mut filled = fill_integer(int_digits, &int_placeholders);
+    if grouping {
+        let joined: String = filled.concat();
+        let trimmed = joined.trim_start();
+        let grouped = group_thousands(trimmed);
+        let padding = joined.len() - trimmed.len();
+        filled = vec![String::new(); filled.len()];
+        if let Some(first) = filled.first_mut() {
+            *first = format!("{}{}", " ".repeat(padding), grouped);
+        }
+    }
+
+    // trailing zeros are shown for `0` only
+    let frac: Vec<char> = frac_digits.chars().collect();
+    let mut frac_out: Vec<String> = frac.iter().map(|c| c.to_string()).collect();
+    for i in (0..frac.len()).rev() {
+        if frac[i] != '0' || decimal_placeholders[i] == '0' {
+            break;
+        }
+        frac_out[i] = if decimal_placeholders[i] == '?' {
+            " ".to_string()
+        } else {
+            String::new()
+        };
+    }
+
+    let mut result = String::new();
+    if minus {
+        result.push('-');
+    }
+    let mut int_index = 0;
+    let mut frac_index = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Digit(_) if i < int_end => {
+                result.push_str(&filled[int_index]);
+                int_index += 1;
+            }
+            Token::Digit(_) if i < number_end => {
+                result.push_str(&frac_out[frac_index]);
+                frac_index += 1;
+            }
+            Token::Point if Some(i) == point => result.push('.'),
+            Token::Exp(plus) => {
+                let digits = tokens[i + 1..].iter().take_while(|t| is_digit(t)).count();
+                if exponent < 0 {
+                    result.push_str("E-");
+                } else {
+                    result.push_str(if *plus { "E+" } else { "E" });
+                }
+                result.push_str(&format!("{:0width$}", exponent.abs(), width = digits));
+                i += digits;
+            }
+            Token::Percent => result.push('%'),
+            Token::Literal(text) => result.push_str(text),
+            Token::General => result.push_str(&format_general(value)),
+            _ => (),
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Closest fraction to `value` with denominator up to `max_denominator`,
+/// by continued fractions, the last convergent is compared with the semiconvergent.
+fn approximate(value: f64, max_denominator: u64) -> (u64, u64) {
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    let mut x = value;
+    // continued fraction of f64 has less than 64 terms
+    for _ in 0..64 {
+        let a = x.floor() as u64;
+        let q2 = a.saturating_mul(q1).saturating_add(q0);
+        if q2 > max_denominator {
+            break;
+        }
+        let p2 = a.saturating_mul(p1).saturating_add(p0);
+        (p0, q0, p1, q1) = (p1, q1, p2, q2);
+        let fract = x - x.floor();
+        if fract < 1e-9 {
+            break;
+        }
+        x = 1.0 / fract;
+    }
+    let k = (max_denominator - q0) / q1;
+    let (p2, q2) = (p0 + k * p1, q0 + k * q1);
+    let error = |p: u64, q: u64| (value - p as f64 / q as f64).abs();
+    if error(p2, q2) < error(p1, q1) {
+        (p2, q2)
+    } else {
+        (p1, q1)
+    }
+}
+
+/// Formats fraction like `# ??/??` (integer part is optional) or `# ?/8` (fixed denominator).
+/// Numerator is right-aligned and denominator is left-aligned in their placeholders.
+fn format_fraction(value: f64, tokens: &[Token], minus: bool) -> String {
+    let is_digit = |t: &Token| matches!(t, Token::Digit(_));
+    let is_literal_digits =
+        |t: &Token| matches!(t, Token::Literal(l) if l.chars().all(|c| c.is_ascii_digit()));
+    let Some(slash) = tokens
+        .iter()
+        .position(|t| matches!(t, Token::Literal(l) if l == "/"))
+    else {
+        return format_general(value);
+    };
+    let numerator_start = slash
+        - tokens[..slash]
+            .iter()
+            .rev()
+            .take_while(|t| is_digit(t))
+            .count();
+    let denominator_end = slash
+        + 1
+        + tokens[slash + 1..]
+            .iter()
+            .take_while(|t| is_digit(t) || is_literal_digits(t))
+            .count();
+    let placeholders = |tokens: &[Token]| -> Vec<char> {
+        tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Digit(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    };
+    let int_placeholders = placeholders(&tokens[..numerator_start]);
+    let numerator_placeholders = placeholders(&tokens[numerator_start..slash]);
+    let denominator_tokens = &tokens[slash + 1..denominator_end];
+    let fixed_denominator = denominator_tokens
+        .iter()
+        .any(is_literal_digits)
+        .then(|| {
+            denominator_tokens
+                .iter()
+                .map(|t| match t {
+                    Token::Digit(c) => c.to_string(),
+                    Token::Literal(l) => l.clone(),
+                    _ => String::new(),
+                })
+                .collect::<String>()
+        })
+        .and_then(|denominator| denominator.parse::<u64>().ok())
+        .filter(|&denominator| denominator > 0);
+
+    let mixed = !int_placeholders.is_empty();
+    let (mut whole, fraction) = if mixed {
+        (value.trunc(), value.fract())
+    } else {
+        (0.0, value)
+    };
+    let (mut numerator, denominator) = match fixed_denominator {
+        Some(denominator) => ((fraction * denominator as f64).round() as u64, denominator),
+        None => {
+            let digits = placeholders(denominator_tokens).len().clamp(1, 9) as u32;
+            approximate(fraction, 10u64.pow(digits) - 1)
+        }
+    };
+    if mixed && numerator == denominator {
+        whole += 1.0;
+        numerator = 0;
+    }
+
+    // the whole number is shown for zero value even with `#`
+    let whole_digits = match whole as u64 {
+        0 if numerator == 0 => "0".to_string(),
+        0 => String::new(),
+        whole => whole.to_string(),
+    };
+    let whole_filled = fill_integer(&whole_digits, &int_placeholders);
+    let numerator_filled = fill_integer(&numerator.to_string(), &numerator_placeholders);
+    let denominator_text = match fixed_denominator {
+        Some(denominator) => denominator.to_string(),
+        None => {
+            let mut text = denominator.to_string();
+            for &placeholder in placeholders(denominator_tokens).iter().skip(text.len()) {
+                match placeholder {
+                    '0' => text.push('0'),
+                    '?' => text.push(' '),
+                    _ => (),
+                }
+            }
+            text
+        }
+    };
+    let mut fraction_text = format!("{}/{}", numerator_filled.concat(), denominator_text);
+    if mixed && numerator == 0 {
+        // fraction of whole numbers is hidden
+        fraction_text = " ".repeat(fraction_text.chars().count());
+    }
+
+    let mut result = String::new();
+    if minus {
+        result.push('-');
+    }
+    let mut int_index = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Digit(_) if i < numerator_start => {
+                result.push_str(&whole_filled[int_index]);
+                int_index += 1;
+            }
+            _ if i == numerator_start => result.push_str(&fraction_text),
+            _ if i > numerator_start && i < denominator_end => (),
+            Token::Literal(text) => result.push_str(text),
+            Token::Percent => result.push('%'),
+            _ => (),
+        }
+    }
+    result
+}
+
+fn format_text(text: &str, tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::Literal(literal) => literal.as_str(),
+            Token::Text => text,
+            _ => "",
+        })
+        .collect()
+}
+
+/// Renders number with format `code`, `date_offset` is added to serials of dates.
+fn format_float(value: f64, code: &str, date_offset: f64) -> String {
+    if !value.is_finite() {
+        return format_general(value);
+    }
+    let sections = split_sections(code);
+    let numeric: Vec<&str> = sections
+        .iter()
+        .take(3)
+        .filter(|section| !section.contains('@') || sections.len() == 1)
+        .copied()
+        .collect();
+    if numeric.is_empty() {
+        return format_general(value);
+    }
+    let conditions: Vec<Option<Condition<'_>>> = numeric
+        .iter()
+        .take(2)
+        .map(|section| section_condition(section))
+        .collect();
+    let (index, minus) = if conditions.iter().any(Option::is_some) {
+        // sections with conditions are checked in order, the next one is for other values
+        let matches = |i: usize| {
+            conditions
+                .get(i)
+                .and_then(Option::as_ref)
+                .is_some_and(|condition| condition.matches(value))
+        };
+        let index = if matches(0) {
+            0
+        } else if matches(1) || conditions.get(1).is_some_and(Option::is_none) {
+            1
+        } else {
+            2
+        };
+        // minus is shown, unless the section is for negative numbers like `[<0]`
+        let negative_section = index == 1
+            && match &conditions[1] {
+                Some(condition) => {
+                    matches!(condition.operator, "<" | "<=" | "=<") && condition.value == 0.0
+                }
+                None => true,
+            };
+        (index, value < 0.0 && !negative_section)
+    } else {
+        let index = match numeric.len() {
+            1 => 0,
+            _ if value < 0.0 => 1,
+            n if n >= 3 && value == 0.0 => 2,
+            _ => 0,
+        };
+        // sections other than the only one are for negative numbers without minus
+        (index, numeric.len() == 1 && value < 0.0)
+    };
+    let section = numeric.get(index).copied().unwrap_or(numeric[0]);
+    let value = value.abs();
+
+    let tokens = tokenize(section);
+    let signed = if minus { -value } else { value };
+    let has_date = tokens.iter().any(|t| matches!(t, Token::Date(_)));
+    let has_digits = tokens.iter().any(|t| matches!(t, Token::Digit(_)));
+    let is_fraction = has_digits
+        && tokens
+            .iter()
+            .any(|t| matches!(t, Token::Literal(l) if l == "/"));
+    if has_date {
+        format_date(signed, date_offset, &tokens)
+    } else if is_fraction {
+        format_fraction(value, &tokens, minus)
+    } else if !has_digits && tokens.contains(&Token::Text) {
+        format_general(signed)
+    } else if !has_digits && !tokens.contains(&Token::General) {
+        // only literals, like `"-"` for zeros
+        format_text("", &tokens)
+    } else {
+        format_number(value, &tokens, minus)
+    }
+}
+
+/// Days between 1900 and 1904 date systems for dates of 1904 workbooks, zero otherwise.
+/// `ExcelDateTime` has no getter for the date system, it is compared with the same
+/// value of 1904 date system instead.
+fn date_offset(value: &ExcelDateTime) -> f64 {
+    let raw = value.as_f64();
+    let is_1904 = [ExcelDateTimeType::DateTime, ExcelDateTimeType::TimeDelta]
+        .into_iter()
+        .any(|kind| *value == ExcelDateTime::new(raw, kind, true));
+    if is_1904 {
+        1462.0
+    } else {
+        0.0
+    }
+}
+
+/// Renders cell value as Excel displays it with number format `code`.
+pub fn format_value(value: &Data, code: &str) -> String {
+    let general = code.is_empty() || code.eq_ignore_ascii_case("general");
+    match value {
+        Data::Int(v) if general => v.to_string(),
+        Data::Int(v) => format_float(*v as f64, code, 0.0),
+        Data::Float(v) if general => format_general(*v),
+        Data::Float(v) => format_float(*v, code, 0.0),
+        Data::DateTime(v) if general => format_general(v.as_f64()),
+        Data::DateTime(v) => format_float(v.as_f64(), code, date_offset(v)),
+        Data::Bool(v) => if *v { "TRUE" } else { "FALSE" }.to_string(),
+        Data::String(v) if general => v.clone(),
+        Data::String(v) => {
+            let sections = split_sections(code);
+            let section = match sections.len() {
+                4 => Some(sections[3]),
+                _ => sections
+                    .iter()
+                    .find(|section| section.contains('@'))
+                    .copied(),
+            };
+            match section.map(tokenize) {
+                Some(tokens) => format_text(v, &tokens),
+                None => v.clone(),
+            }
+        }
+        Data::DateTimeIso(v) | Data::DurationIso(v) => v.clone(),
+        Data::Error(e) => e.to_string(),
+        Data::Empty => String::new(),
+    }
+}
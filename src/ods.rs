@@ -0,0 +1,123 @@
+//! Parts of ods, which calamine doesn't expose.
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use calamine::{Dimensions, OdsError};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+fn get_attribute<B: BufRead>(
+    xml: &XmlReader<B>,
+    attributes: Attributes<'_>,
+    key: &[u8],
+) -> Result<Option<String>, OdsError> {
+    for attribute in attributes {
+        let attribute = attribute.map_err(OdsError::XmlAttr)?;
+        if attribute.key == QName(key) {
+            return Ok(Some(attribute.decode_and_unescape_value(xml)?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn get_count<B: BufRead>(
+    xml: &XmlReader<B>,
+    e: &BytesStart<'_>,
+    key: &[u8],
+) -> Result<u32, OdsError> {
+    Ok(get_attribute(xml, e.attributes(), key)?
+        .and_then(|count| count.parse::<u32>().ok())
+        .unwrap_or(1)
+        .max(1))
+}
+
+/// Returns merged regions (cells with `table:number-columns-spanned`
+/// or `table:number-rows-spanned`) of worksheet `name`.
+pub fn worksheet_merge_cells<RS: Read + Seek>(
+    reader: RS,
+    name: &str,
+) -> Result<Vec<Dimensions>, OdsError> {
+    let mut zip = ZipArchive::new(reader).map_err(OdsError::Zip)?;
+    let file = match zip.by_name("content.xml") {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(OdsError::FileNotFound("content.xml"))
+        }
+        Err(e) => return Err(OdsError::Zip(e)),
+    };
+    let mut xml = XmlReader::from_reader(BufReader::new(file));
+
+    let mut found = false;
+    // depth of tables nested in cells of the sheet
+    let mut depth = 0;
+    let mut merged = Vec::new();
+    let mut row = 0u32;
+    let mut col = 0u32;
+    let mut rows_repeated = 1;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.name() == QName(b"table:table") => {
+                if found {
+                    depth += 1;
+                } else if get_attribute(&xml, e.attributes(), b"table:name")?.as_deref()
+                    == Some(name)
+                {
+                    found = true;
+                }
+            }
+            Event::Empty(ref e)
+                if !found
+                    && e.name() == QName(b"table:table")
+                    && get_attribute(&xml, e.attributes(), b"table:name")?.as_deref()
+                        == Some(name) =>
+            {
+                return Ok(merged);
+            }
+            Event::End(ref e) if found && e.name() == QName(b"table:table") => {
+                if depth == 0 {
+                    return Ok(merged);
+                }
+                depth -= 1;
+            }
+            Event::Start(ref e) if found && depth == 0 && e.name() == QName(b"table:table-row") => {
+                rows_repeated = get_count(&xml, e, b"table:number-rows-repeated")?;
+                col = 0;
+            }
+            Event::Empty(ref e) if found && depth == 0 && e.name() == QName(b"table:table-row") => {
+                row = row.saturating_add(get_count(&xml, e, b"table:number-rows-repeated")?);
+            }
+            Event::End(ref e) if found && depth == 0 && e.name() == QName(b"table:table-row") => {
+                row = row.saturating_add(rows_repeated);
+            }
+            Event::Start(ref e) | Event::Empty(ref e)
+                if found
+                    && depth == 0
+                    && matches!(
+                        e.name().as_ref(),
+                        b"table:table-cell" | b"table:covered-table-cell"
+                    ) =>
+            {
+                let cols = get_count(&xml, e, b"table:number-columns-spanned")?;
+                let rows = get_count(&xml, e, b"table:number-rows-spanned")?;
+                if e.name() == QName(b"table:table-cell") && (cols > 1 || rows > 1) {
+                    for repeated in 0..rows_repeated {
+                        let start = (row.saturating_add(repeated), col);
+                        let end = (
+                            start.0.saturating_add(rows - 1),
+                            start.1.saturating_add(cols - 1),
+                        );
+                        merged.push(Dimensions { start, end });
+                    }
+                }
+                col = col.saturating_add(get_count(&xml, e, b"table:number-columns-repeated")?);
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Err(OdsError::WorksheetNotFound(name.to_string()))
+}
@@ -0,0 +1,272 @@
+use std::ffi::CString;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, DurationMicrosecondBuilder, Float64Builder, Int64Builder,
+    StringBuilder, Time64MicrosecondBuilder, TimestampMicrosecondBuilder,
+};
+use arrow_array::ffi_stream::FFI_ArrowArrayStream;
+use arrow_array::{ArrayRef, NullArray, RecordBatch, RecordBatchIterator, RecordBatchOptions};
+use arrow_schema::{ArrowError, DataType, Field, Schema, TimeUnit};
+use calamine::{Data, Range};
+use chrono::{NaiveDate, Timelike};
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+use crate::utils::header_names;
+use crate::{CellValue, DatesMode};
+
+/// Arrow type of column, inferred from its cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColumnType {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Date,
+    Time,
+    DateTime,
+    Timedelta,
+    String,
+}
+
+impl ColumnType {
+    fn of(value: &CellValue) -> Self {
+        match value {
+            CellValue::Int(_) => ColumnType::Int,
+            CellValue::Float(_) => ColumnType::Float,
+            CellValue::String(_) => ColumnType::String,
+            CellValue::Time(_) => ColumnType::Time,
+            CellValue::Date(_) => ColumnType::Date,
+            CellValue::DateTime(_) => ColumnType::DateTime,
+            CellValue::Timedelta(_) => ColumnType::Timedelta,
+            CellValue::Bool(_) => ColumnType::Bool,
+            CellValue::Error(_) | CellValue::Empty => ColumnType::Null,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ColumnType::Null, b) => b,
+            (a, ColumnType::Null) => a,
+            (ColumnType::Int, ColumnType::Float) | (ColumnType::Float, ColumnType::Int) => {
+                ColumnType::Float
+            }
+            (ColumnType::Date, ColumnType::DateTime) | (ColumnType::DateTime, ColumnType::Date) => {
+                ColumnType::DateTime
+            }
+            _ => ColumnType::String,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            ColumnType::Null => DataType::Null,
+            ColumnType::Bool => DataType::Boolean,
+            ColumnType::Int => DataType::Int64,
+            ColumnType::Float => DataType::Float64,
+            ColumnType::Date => DataType::Date32,
+            ColumnType::Time => DataType::Time64(TimeUnit::Microsecond),
+            ColumnType::DateTime => DataType::Timestamp(TimeUnit::Microsecond, None),
+            ColumnType::Timedelta => DataType::Duration(TimeUnit::Microsecond),
+            ColumnType::String => DataType::Utf8,
+        }
+    }
+}
+
+fn build_column(typ: ColumnType, values: &[CellValue]) -> ArrayRef {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    match typ {
+        ColumnType::Null => Arc::new(NullArray::new(values.len())),
+        ColumnType::Bool => {
+            let mut builder = BooleanBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Bool(v) => builder.append_value(*v),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::Int => {
+            let mut builder = Int64Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Int(v) => builder.append_value(*v),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::Float => {
+            let mut builder = Float64Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Int(v) => builder.append_value(*v as f64),
+                    CellValue::Float(v) => builder.append_value(*v),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::Date => {
+            let mut builder = Date32Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Date(v) => {
+                        builder.append_value(v.signed_duration_since(epoch).num_days() as i32)
+                    }
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::Time => {
+            let mut builder = Time64MicrosecondBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Time(v) => builder.append_value(
+                        v.num_seconds_from_midnight() as i64 * 1_000_000
+                            + v.nanosecond() as i64 / 1_000,
+                    ),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::DateTime => {
+            let mut builder = TimestampMicrosecondBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::DateTime(v) => builder.append_value(v.and_utc().timestamp_micros()),
+                    CellValue::Date(v) => builder
+                        .append_value(v.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp_micros()),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::Timedelta => {
+            let mut builder = DurationMicrosecondBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    CellValue::Timedelta(v) => builder.append_option(v.num_microseconds()),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        ColumnType::String => {
+            let mut builder = StringBuilder::with_capacity(values.len(), values.len() * 8);
+            for value in values {
+                match value {
+                    CellValue::Error(_) | CellValue::Empty => builder.append_null(),
+                    _ => builder.append_value(value.to_string()),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+/// Builds record batch from `columns` (positions in range) of range. Rows before `header_row`
+/// are skipped, column names are taken from `header_row` (relative to range start).
+pub fn range_to_record_batch(
+    range: &Range<Data>,
+    columns: &[usize],
+    header_row: Option<usize>,
+    dates: DatesMode,
+) -> Result<RecordBatch, ArrowError> {
+    let width = range.width();
+    let (header, rows) = match header_row {
+        Some(header_row) => (
+            range.rows().nth(header_row),
+            range.rows().skip(header_row + 1).collect::<Vec<_>>(),
+        ),
+        None => (None, range.rows().collect::<Vec<_>>()),
+    };
+
+    let names = match header_row {
+        Some(_) => header_names(&Vec::from_iter(header), width),
+        None => (0..width).map(|i| format!("column_{}", i)).collect(),
+    };
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+    for &col in columns {
+        let name = names[col].clone();
+        let values: Vec<CellValue> = rows
+            .iter()
+            .map(|row| CellValue::convert(&row[col], dates))
+            .collect();
+        let typ = values.iter().fold(ColumnType::Null, |typ, value| {
+            typ.merge(ColumnType::of(value))
+        });
+        fields.push(Field::new(name, typ.data_type(), true));
+        arrays.push(build_column(typ, &values));
+    }
+
+    RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+    )
+}
+
+/// Sheet data converted to Arrow, exported via the Arrow PyCapsule interface.
+#[pyclass]
+pub struct CalamineArrowTable {
+    batch: RecordBatch,
+}
+
+impl CalamineArrowTable {
+    pub fn new(batch: RecordBatch) -> Self {
+        CalamineArrowTable { batch }
+    }
+}
+
+#[pymethods]
+impl CalamineArrowTable {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "CalamineArrowTable(num_rows={}, num_columns={})",
+            self.batch.num_rows(),
+            self.batch.num_columns()
+        ))
+    }
+
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // requested schema is optional for producers and isn't supported
+        let _ = requested_schema;
+        to_stream_capsule(py, self.batch.clone())
+    }
+}
+
+pub fn to_stream_capsule(py: Python<'_>, batch: RecordBatch) -> PyResult<Bound<'_, PyCapsule>> {
+    let schema = batch.schema();
+    let reader = RecordBatchIterator::new(vec![Ok(batch)], schema);
+    let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+    let name = CString::new("arrow_array_stream").unwrap();
+    PyCapsule::new_bound(py, stream, Some(name))
+}
@@ -1,7 +1,350 @@
 use std::convert::From;
+use std::fmt::Display;
+use std::sync::Arc;
 
-use calamine::DataType;
+use calamine::{CellErrorType, Data, DataType};
+use pyo3::class::basic::CompareOp;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyType;
+
+use crate::numfmt::format_value;
+use crate::xlsx::NumberFormats;
+use crate::CellValueError;
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellErrorTypeEnum {
+    /// Division by 0 error
+    Div0,
+    /// Unavailable value error
+    NA,
+    /// Invalid name error
+    Name,
+    /// Null value error
+    Null,
+    /// Number error
+    Num,
+    /// Invalid cell reference error
+    Ref,
+    /// Value error
+    Value,
+    /// Getting data
+    GettingData,
+}
+
+impl Display for CellErrorTypeEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CellErrorTypeEnum.{:?}", self)
+    }
+}
+
+impl From<&CellErrorType> for CellErrorTypeEnum {
+    fn from(value: &CellErrorType) -> Self {
+        match value {
+            CellErrorType::Div0 => Self::Div0,
+            CellErrorType::NA => Self::NA,
+            CellErrorType::Name => Self::Name,
+            CellErrorType::Null => Self::Null,
+            CellErrorType::Num => Self::Num,
+            CellErrorType::Ref => Self::Ref,
+            CellErrorType::Value => Self::Value,
+            CellErrorType::GettingData => Self::GettingData,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct CellError {
+    #[pyo3(get)]
+    typ: CellErrorTypeEnum,
+    #[pyo3(get)]
+    text: String,
+}
+
+#[pymethods]
+impl CellError {
+    // implementation of some methods for testing
+    #[new]
+    fn py_new(typ: CellErrorTypeEnum, text: &str) -> Self {
+        CellError {
+            typ,
+            text: text.to_string(),
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("CellError(typ={}, text='{}')", self.typ, self.text))
+    }
+
+    fn __str__(&self) -> String {
+        self.text.clone()
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        match op {
+            CompareOp::Eq => self.eq(other).into_py(py),
+            CompareOp::Ne => self.ne(other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+}
+
+impl From<&CellErrorType> for CellError {
+    fn from(value: &CellErrorType) -> Self {
+        CellError {
+            typ: CellErrorTypeEnum::from(value),
+            text: value.to_string(),
+        }
+    }
+}
+
+/// How error cells (`#DIV/0!`, `#N/A`, ...) are returned to Python.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ErrorsMode {
+    /// Return `CellError` object
+    #[default]
+    Value,
+    /// Return `None`
+    None,
+    /// Return error text, e.g. `#DIV/0!`
+    String,
+    /// Raise `CellValueError`
+    Raise,
+}
+
+impl<'py> FromPyObject<'py> for ErrorsMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "value" => Ok(ErrorsMode::Value),
+            "none" => Ok(ErrorsMode::None),
+            "string" => Ok(ErrorsMode::String),
+            "raise" => Ok(ErrorsMode::Raise),
+            other => Err(PyValueError::new_err(format!(
+                "errors must be one of 'value', 'none', 'string' or 'raise', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// How Excel dates (numbers with date format) are returned to Python.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DatesMode {
+    /// `date`, `time`, `datetime` or `timedelta`, depending on value
+    #[default]
+    Infer,
+    /// Excel serial number as `float`
+    Raw,
+    /// Always `datetime` (`timedelta` for durations)
+    DateTime,
+}
+
+impl<'py> FromPyObject<'py> for DatesMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "infer" => Ok(DatesMode::Infer),
+            "raw" => Ok(DatesMode::Raw),
+            "datetime" => Ok(DatesMode::DateTime),
+            other => Err(PyValueError::new_err(format!(
+                "dates must be one of 'infer', 'raw' or 'datetime', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl ToPyObject for DatesMode {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        match self {
+            DatesMode::Infer => "infer",
+            DatesMode::Raw => "raw",
+            DatesMode::DateTime => "datetime",
+        }
+        .to_object(py)
+    }
+}
+
+/// How values of cells are returned by `to_python`/`iter_rows`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValuesMode {
+    /// Python objects of the cell type
+    #[default]
+    Typed,
+    /// Text rendered with number format of cell, like Excel displays it
+    Formatted,
+}
+
+impl<'py> FromPyObject<'py> for ValuesMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "typed" => Ok(ValuesMode::Typed),
+            "formatted" => Ok(ValuesMode::Formatted),
+            other => Err(PyValueError::new_err(format!(
+                "values must be one of 'typed' or 'formatted', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// How float cells are returned to Python.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FloatsMode {
+    #[default]
+    Float,
+    /// `decimal.Decimal` from the shortest representation, which round-trips to the float
+    Decimal,
+}
+
+impl<'py> FromPyObject<'py> for FloatsMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "float" => Ok(FloatsMode::Float),
+            "decimal" => Ok(FloatsMode::Decimal),
+            other => Err(PyValueError::new_err(format!(
+                "floats must be one of 'float' or 'decimal', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Which Python type numbers get, to have the same types for xls, xlsx and ods.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NumbersMode {
+    /// `int` or `float`, as stored in the file
+    #[default]
+    Keep,
+    /// `int` for floats, which are whole numbers in the range of i64
+    Int,
+    /// Always `float`
+    Float,
+}
+
+impl<'py> FromPyObject<'py> for NumbersMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "keep" => Ok(NumbersMode::Keep),
+            "int" => Ok(NumbersMode::Int),
+            "float" => Ok(NumbersMode::Float),
+            other => Err(PyValueError::new_err(format!(
+                "numbers must be one of 'keep', 'int' or 'float', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+static DECIMAL: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+/// Converts `value` to `decimal.Decimal`, `0.1` becomes `Decimal("0.1")`.
+fn to_decimal(py: Python<'_>, value: f64) -> PyResult<PyObject> {
+    let decimal = DECIMAL.get_or_try_init(py, || {
+        py.import_bound("decimal")?
+            .getattr("Decimal")?
+            .downcast_into::<PyType>()
+            .map(Bound::unbind)
+            .map_err(PyErr::from)
+    })?;
+    // `Display` of f64 is the shortest representation, which parses back to the same value
+    Ok(decimal.bind(py).call1((value.to_string(),))?.unbind())
+}
+
+/// Value returned for empty cells, passed to `to_python`/`iter_rows`.
+/// `Default` means that the argument is omitted and the sheet's value is used.
+pub enum EmptyValueArg {
+    Default,
+    Value(PyObject),
+}
+
+impl<'py> FromPyObject<'py> for EmptyValueArg {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(EmptyValueArg::Value(ob.clone().unbind()))
+    }
+}
+
+/// Options of converting cell values to Python objects.
+#[derive(Debug)]
+pub struct ConvertOptions {
+    pub errors: ErrorsMode,
+    /// Value for empty cells, `""` if not set.
+    pub empty_value: Option<PyObject>,
+    pub dates: DatesMode,
+    pub floats: FloatsMode,
+    pub numbers: NumbersMode,
+    /// Number formats of cells, values are rendered as text if set.
+    pub number_formats: Option<Arc<NumberFormats>>,
+}
+
+impl ConvertOptions {
+    /// `default` is the sheet's or workbook's value, used when `empty_value` is omitted.
+    pub fn new(
+        py: Python<'_>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        default: Option<&PyObject>,
+    ) -> Self {
+        let empty_value = match empty_value {
+            EmptyValueArg::Default => default.map(|v| v.clone_ref(py)),
+            EmptyValueArg::Value(value) => Some(value),
+        };
+        ConvertOptions {
+            errors,
+            empty_value,
+            dates: DatesMode::Infer,
+            floats: FloatsMode::Float,
+            numbers: NumbersMode::Keep,
+            number_formats: None,
+        }
+    }
+
+    pub fn with_dates(mut self, dates: DatesMode) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    pub fn with_floats(mut self, floats: FloatsMode) -> Self {
+        self.floats = floats;
+        self
+    }
+
+    pub fn with_numbers(mut self, numbers: NumbersMode) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    pub fn with_number_formats(mut self, number_formats: Option<Arc<NumberFormats>>) -> Self {
+        self.number_formats = number_formats;
+        self
+    }
+
+    /// Converts `value` at absolute `position` to Python object,
+    /// as formatted text if number formats are set.
+    pub fn to_object(
+        &self,
+        py: Python<'_>,
+        value: &Data,
+        position: (u32, u32),
+    ) -> PyResult<PyObject> {
+        match &self.number_formats {
+            Some(formats) if !value.is_empty() && !value.is_error() => {
+                let code = formats.get(position).unwrap_or("General");
+                Ok(format_value(value, code).to_object(py))
+            }
+            _ => CellValue::convert(value, self.dates).to_object_with(py, self, position),
+        }
+    }
+
+    pub fn empty_to_object(&self, py: Python<'_>) -> PyObject {
+        match &self.empty_value {
+            Some(value) => value.clone_ref(py),
+            None => "".to_object(py),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum CellValue {
@@ -13,9 +356,93 @@ pub enum CellValue {
     DateTime(chrono::NaiveDateTime),
     Timedelta(chrono::Duration),
     Bool(bool),
+    Error(CellErrorType),
     Empty,
 }
 
+impl CellValue {
+    /// Converts cell value like `From`, but Excel dates are converted according to `dates`.
+    pub fn convert<DT: DataType>(value: &DT, dates: DatesMode) -> Self {
+        match dates {
+            DatesMode::Raw if value.is_datetime() => {
+                CellValue::Float(value.get_datetime().unwrap().as_f64())
+            }
+            DatesMode::Raw if value.is_datetime_iso() => value
+                .get_datetime_iso()
+                .map(|s| CellValue::String(s.to_owned()))
+                .unwrap_or(CellValue::Empty),
+            DatesMode::Raw if value.is_duration_iso() => value
+                .get_duration_iso()
+                .map(|s| CellValue::String(s.to_owned()))
+                .unwrap_or(CellValue::Empty),
+            DatesMode::DateTime if value.is_datetime() => {
+                let dt = value.get_datetime().unwrap();
+                if dt.is_duration() {
+                    value.as_duration().map(CellValue::Timedelta)
+                } else {
+                    value.as_datetime().map(CellValue::DateTime)
+                }
+                .unwrap_or(CellValue::Float(dt.as_f64()))
+            }
+            DatesMode::DateTime => match CellValue::from(value) {
+                CellValue::Date(v) => CellValue::DateTime(v.and_hms_opt(0, 0, 0).unwrap()),
+                other => other,
+            },
+            _ => CellValue::from(value),
+        }
+    }
+
+    /// Converts value to Python object, applying `options` to error and empty cells.
+    /// `position` is the absolute (row, col) of the cell, used in the error message.
+    pub fn to_object_with(
+        &self,
+        py: Python<'_>,
+        options: &ConvertOptions,
+        position: (u32, u32),
+    ) -> PyResult<PyObject> {
+        match (self, options.errors) {
+            (CellValue::Empty, _) => Ok(options.empty_to_object(py)),
+            (CellValue::Error(_), ErrorsMode::Value) => Ok(self.to_object(py)),
+            (CellValue::Error(_), ErrorsMode::None) => Ok(py.None()),
+            (CellValue::Error(e), ErrorsMode::String) => Ok(e.to_string().to_object(py)),
+            (CellValue::Error(e), ErrorsMode::Raise) => Err(CellValueError::new_err(format!(
+                "Cell ({}, {}) contains error '{}'",
+                position.0, position.1, e
+            ))),
+            (CellValue::Float(v), _)
+                if options.numbers == NumbersMode::Int
+                    && v.fract() == 0.0
+                    && *v >= i64::MIN as f64
+                    && *v < i64::MAX as f64 =>
+            {
+                Ok((*v as i64).to_object(py))
+            }
+            (CellValue::Int(v), _) if options.numbers == NumbersMode::Float => {
+                CellValue::Float(*v as f64).to_object_with(py, options, position)
+            }
+            (CellValue::Float(v), _) if options.floats == FloatsMode::Decimal => to_decimal(py, *v),
+            _ => Ok(self.to_object(py)),
+        }
+    }
+}
+
+impl Display for CellValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellValue::Int(v) => write!(f, "{}", v),
+            CellValue::Float(v) => write!(f, "{}", v),
+            CellValue::String(v) => write!(f, "{}", v),
+            CellValue::Time(v) => write!(f, "{}", v),
+            CellValue::Date(v) => write!(f, "{}", v),
+            CellValue::DateTime(v) => write!(f, "{}", v),
+            CellValue::Timedelta(v) => write!(f, "{}", v),
+            CellValue::Bool(v) => write!(f, "{}", v),
+            CellValue::Error(v) => write!(f, "{}", v),
+            CellValue::Empty => Ok(()),
+        }
+    }
+}
+
 impl IntoPy<PyObject> for CellValue {
     fn into_py(self, py: Python) -> PyObject {
         self.to_object(py)
@@ -33,11 +460,60 @@ impl ToPyObject for CellValue {
             CellValue::Date(v) => v.to_object(py),
             CellValue::DateTime(v) => v.to_object(py),
             CellValue::Timedelta(v) => v.to_object(py),
+            CellValue::Error(v) => CellError::from(v).into_py(py),
             CellValue::Empty => "".to_object(py),
         }
     }
 }
 
+/// Parses ISO 8601 duration like `PT255H10M10.1S` or `-P1DT2H`, as written by ods.
+/// Years and months have no fixed length, durations with them are not parsed.
+fn parse_duration_iso(value: &str) -> Option<chrono::Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+    let value = value.strip_prefix('P')?;
+    let (date, time) = value.split_once('T').unwrap_or((value, ""));
+    if value.is_empty() || value.ends_with('T') {
+        return None;
+    }
+
+    let mut duration = chrono::Duration::zero();
+    let mut parse_part = |part: &str, units: &[(char, i64)]| -> Option<()> {
+        let mut rest = part;
+        while !rest.is_empty() {
+            let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')?;
+            let (number, unit) = (&rest[..end], rest[end..].chars().next()?);
+            rest = &rest[end + unit.len_utf8()..];
+            let seconds = units.iter().find(|(u, _)| *u == unit)?.1;
+            let (whole, fraction) = number.split_once(['.', ',']).unwrap_or((number, ""));
+            let whole: i64 = whole.parse().ok()?;
+            // nanoseconds of the fraction, digits after the 9th are dropped
+            let fraction: i64 = format!("{:0<9}", &fraction[..fraction.len().min(9)])
+                .parse()
+                .ok()?;
+            if seconds == 0 {
+                // years and months
+                if whole != 0 || fraction != 0 {
+                    return None;
+                }
+                continue;
+            }
+            duration = duration
+                .checked_add(&chrono::Duration::try_seconds(whole.checked_mul(seconds)?)?)?
+                .checked_add(&chrono::Duration::nanoseconds(
+                    fraction.checked_mul(seconds)?,
+                ))?;
+        }
+        Some(())
+    };
+    parse_part(date, &[('Y', 0), ('M', 0), ('W', 604_800), ('D', 86_400)])?;
+    parse_part(time, &[('H', 3_600), ('M', 60), ('S', 1)])?;
+
+    Some(if negative { -duration } else { duration })
+}
+
 impl<DT> From<&DT> for CellValue
 where
     DT: DataType,
@@ -82,17 +558,24 @@ where
             }
             .unwrap_or(CellValue::String(v.to_owned()))
         } else if value.is_duration_iso() {
-            value.as_time().map(CellValue::Time).unwrap_or(
-                value
-                    .get_duration_iso()
-                    .map(|s| CellValue::String(s.to_owned()))
-                    .unwrap_or(CellValue::Empty),
-            )
+            value
+                .get_duration_iso()
+                .map(|s| {
+                    parse_duration_iso(s)
+                        .map(CellValue::Timedelta)
+                        .unwrap_or(CellValue::String(s.to_owned()))
+                })
+                .unwrap_or(CellValue::Empty)
         } else if value.is_bool() {
             value
                 .get_bool()
                 .map(CellValue::Bool)
                 .unwrap_or(CellValue::Empty)
+        } else if value.is_error() {
+            value
+                .get_error()
+                .map(|e| CellValue::Error(e.clone()))
+                .unwrap_or(CellValue::Empty)
         } else {
             CellValue::Empty
         }
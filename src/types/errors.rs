@@ -14,3 +14,4 @@ create_exception!(python_calamine, WorksheetNotFound, CalamineError);
 create_exception!(python_calamine, XmlError, CalamineError);
 create_exception!(python_calamine, ZipError, CalamineError);
 create_exception!(python_calamine, WorkbookClosed, CalamineError);
+create_exception!(python_calamine, CellValueError, CalamineError);
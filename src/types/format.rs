@@ -0,0 +1,104 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use calamine::Error as CalamineCrateError;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::PyString;
+
+use crate::detect::{detect_format, Format};
+use crate::types::source::WorkbookData;
+use crate::types::WorkbookFormat;
+use crate::utils::err_to_py;
+use crate::Error;
+
+/// Format of file detected by `detect_format`.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct DetectedFormat {
+    /// Format accepted by `format` argument, `None` for encrypted and unknown files
+    #[pyo3(get)]
+    format: Option<WorkbookFormat>,
+    /// BIFF version of xls
+    #[pyo3(get)]
+    biff_version: Option<u8>,
+    /// Password protected xlsx, xlsm or xlsb
+    #[pyo3(get)]
+    encrypted: bool,
+}
+
+#[pymethods]
+impl DetectedFormat {
+    // implementation of some methods for testing
+    #[new]
+    #[pyo3(signature = (format=None, biff_version=None, encrypted=false))]
+    fn py_new(format: Option<WorkbookFormat>, biff_version: Option<u8>, encrypted: bool) -> Self {
+        DetectedFormat {
+            format,
+            biff_version,
+            encrypted,
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let mut repr = match self.format {
+            Some(format) => format!("DetectedFormat(format='{}'", format.as_str()),
+            None => "DetectedFormat(format=None".to_string(),
+        };
+        if let Some(version) = self.biff_version {
+            repr.push_str(&format!(", biff_version={}", version));
+        }
+        if self.encrypted {
+            repr.push_str(", encrypted=True");
+        }
+        repr.push(')');
+        Ok(repr)
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        match op {
+            CompareOp::Eq => self.eq(other).into_py(py),
+            CompareOp::Ne => self.ne(other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+}
+
+impl From<Format> for DetectedFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Workbook(format, biff_version) => {
+                DetectedFormat::py_new(Some(format), biff_version, false)
+            }
+            Format::Encrypted => DetectedFormat::py_new(None, None, true),
+            Format::Unknown => DetectedFormat::py_new(None, None, false),
+        }
+    }
+}
+
+impl DetectedFormat {
+    pub fn from_object(py: Python<'_>, path_or_bytes: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let path = if let Ok(string_ref) = path_or_bytes.downcast::<PyString>() {
+            string_ref.to_string_lossy().to_string()
+        } else if let Ok(path) = path_or_bytes.extract::<PathBuf>() {
+            path.to_string_lossy().to_string()
+        } else if let Ok(buffer) = PyBuffer::<u8>::get_bound(path_or_bytes) {
+            let data = WorkbookData::from_buffer(py, buffer)?;
+            return py
+                .allow_threads(|| detect_format(|| Ok(data.reader())))
+                .map(Self::from)
+                .map_err(|e| err_to_py(Error::Calamine(CalamineCrateError::Io(e))));
+        } else {
+            return Err(PyTypeError::new_err(
+                "path_or_bytes must be str, os.PathLike or bytes-like object",
+            ));
+        };
+
+        py.allow_threads(|| detect_format(|| File::open(&path).map(BufReader::new)))
+            .map(Self::from)
+            .map_err(|e| err_to_py(Error::Calamine(CalamineCrateError::Io(e))))
+    }
+}
@@ -1,10 +1,26 @@
+mod arrow;
 mod cell;
 mod errors;
+mod format;
+mod numpy;
+mod records;
 mod sheet;
+mod skiprows;
+mod source;
+mod stream;
+mod table;
+mod usecols;
 mod workbook;
-pub use cell::CellValue;
+pub use arrow::CalamineArrowTable;
+pub use cell::{
+    CellError, CellErrorTypeEnum, CellValue, ConvertOptions, DatesMode, EmptyValueArg, ErrorsMode,
+    FloatsMode, NumbersMode, ValuesMode,
+};
 pub use errors::{
-    CalamineError, Error, PasswordError, WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
+    CalamineError, CellValueError, Error, PasswordError, WorkbookClosed, WorksheetNotFound,
+    XmlError, ZipError,
 };
+pub use format::DetectedFormat;
 pub use sheet::{CalamineSheet, SheetMetadata, SheetTypeEnum, SheetVisibleEnum};
-pub use workbook::CalamineWorkbook;
+pub use table::CalamineTable;
+pub use workbook::{CalamineWorkbook, WorkbookFormat};
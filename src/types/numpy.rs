@@ -0,0 +1,281 @@
+//! NumPy export without linking to NumPy: values are written straight into `numpy.empty`
+//! byte array through the buffer protocol, which is then viewed as `dtype`.
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use calamine::{Data, Range};
+use chrono::NaiveDateTime;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+
+use crate::utils::column_name;
+use crate::CellValue;
+
+/// Type of array values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Float32,
+    Float64,
+    Int32,
+    Int64,
+    /// `datetime64` with unit (`s`, `ms`, `us` or `ns`) and number of units per second
+    DateTime(&'static str, i64),
+}
+
+impl Kind {
+    fn from_dtype(numpy: &Bound<'_, PyModule>, dtype: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let dtype = numpy.getattr("dtype")?.call1((dtype,))?;
+        let kind: String = dtype.getattr("kind")?.extract()?;
+        let itemsize: usize = dtype.getattr("itemsize")?.extract()?;
+        let result = match (kind.as_str(), itemsize) {
+            ("f", 8) => Some(Kind::Float64),
+            ("f", 4) => Some(Kind::Float32),
+            ("i", 8) => Some(Kind::Int64),
+            ("i", 4) => Some(Kind::Int32),
+            ("M", 8) => {
+                let (unit, count): (String, i64) = numpy
+                    .getattr("datetime_data")?
+                    .call1((&dtype,))?
+                    .extract()?;
+                match (unit.as_str(), count) {
+                    ("s", 1) => Some(Kind::DateTime("s", 1)),
+                    ("ms", 1) => Some(Kind::DateTime("ms", 1_000)),
+                    ("us", 1) => Some(Kind::DateTime("us", 1_000_000)),
+                    ("ns", 1) => Some(Kind::DateTime("ns", 1_000_000_000)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        result.ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "dtype must be float32, float64, int32, int64 or datetime64[s|ms|us|ns], got '{}'",
+                dtype
+            ))
+        })
+    }
+
+    /// `datetime64[us]` for columns of dates, `float64` for others.
+    fn infer(rows: &[&[Data]], col: usize) -> Self {
+        let mut is_datetime = false;
+        for row in rows {
+            match CellValue::from(&row[col]) {
+                CellValue::Date(_) | CellValue::DateTime(_) => is_datetime = true,
+                CellValue::Empty | CellValue::Error(_) => (),
+                _ => return Kind::Float64,
+            }
+        }
+        if is_datetime {
+            Kind::DateTime("us", 1_000_000)
+        } else {
+            Kind::Float64
+        }
+    }
+
+    fn dtype(self) -> String {
+        match self {
+            Kind::Float32 => "float32".to_string(),
+            Kind::Float64 => "float64".to_string(),
+            Kind::Int32 => "int32".to_string(),
+            Kind::Int64 => "int64".to_string(),
+            Kind::DateTime(unit, _) => format!("datetime64[{}]", unit),
+        }
+    }
+
+    fn itemsize(self) -> usize {
+        match self {
+            Kind::Float32 | Kind::Int32 => 4,
+            Kind::Float64 | Kind::Int64 | Kind::DateTime(..) => 8,
+        }
+    }
+
+    /// Writes `value` to `item` (bytes of array item), returns `true` if value is missing
+    /// and must be masked. Empty and error cells are NaN for floats and NaT for dates.
+    fn write(self, value: &Data, position: (u32, u32), item: &[Cell<u8>]) -> PyResult<bool> {
+        match self {
+            Kind::Float32 => {
+                let value = to_float(value, self, position)?.unwrap_or(f64::NAN);
+                write_bytes(item, &(value as f32).to_ne_bytes());
+                Ok(false)
+            }
+            Kind::Float64 => {
+                let value = to_float(value, self, position)?.unwrap_or(f64::NAN);
+                write_bytes(item, &value.to_ne_bytes());
+                Ok(false)
+            }
+            Kind::Int32 => {
+                let value = to_int(value, self, position)?;
+                let value = value
+                    .map(i32::try_from)
+                    .transpose()
+                    .map_err(|_| cast_error(self, position))?;
+                write_bytes(item, &value.unwrap_or_default().to_ne_bytes());
+                Ok(value.is_none())
+            }
+            Kind::Int64 => {
+                let value = to_int(value, self, position)?;
+                write_bytes(item, &value.unwrap_or_default().to_ne_bytes());
+                Ok(value.is_none())
+            }
+            Kind::DateTime(_, per_second) => {
+                let value = match to_datetime(value, self, position)? {
+                    Some(dt) => dt
+                        .and_utc()
+                        .timestamp()
+                        .checked_mul(per_second)
+                        .and_then(|ts| {
+                            ts.checked_add(
+                                dt.and_utc().timestamp_subsec_nanos() as i64
+                                    / (1_000_000_000 / per_second),
+                            )
+                        })
+                        .ok_or_else(|| cast_error(self, position))?,
+                    // NaT
+                    None => i64::MIN,
+                };
+                write_bytes(item, &value.to_ne_bytes());
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn write_bytes(item: &[Cell<u8>], bytes: &[u8]) {
+    for (cell, &byte) in item.iter().zip(bytes) {
+        cell.set(byte);
+    }
+}
+
+fn cast_error(kind: Kind, position: (u32, u32)) -> PyErr {
+    PyValueError::new_err(format!(
+        "Cell ({}, {}) can't be converted to {}",
+        position.0,
+        position.1,
+        kind.dtype()
+    ))
+}
+
+/// Numbers, bools, Excel dates (as serial number) and numeric strings.
+fn to_float(value: &Data, kind: Kind, position: (u32, u32)) -> PyResult<Option<f64>> {
+    match value {
+        Data::Int(v) => Ok(Some(*v as f64)),
+        Data::Float(v) => Ok(Some(*v)),
+        Data::Bool(v) => Ok(Some(*v as u8 as f64)),
+        Data::DateTime(v) => Ok(Some(v.as_f64())),
+        Data::String(v) if v.trim().is_empty() => Ok(None),
+        Data::String(v) => v
+            .trim()
+            .parse::<f64>()
+            .map(Some)
+            .map_err(|_| cast_error(kind, position)),
+        Data::Empty | Data::Error(_) => Ok(None),
+        Data::DateTimeIso(_) | Data::DurationIso(_) => Err(cast_error(kind, position)),
+    }
+}
+
+/// Like `to_float`, but fractional numbers are not truncated.
+fn to_int(value: &Data, kind: Kind, position: (u32, u32)) -> PyResult<Option<i64>> {
+    if let Data::Int(v) = value {
+        return Ok(Some(*v));
+    }
+    match to_float(value, kind, position)? {
+        Some(v) if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 => {
+            Ok(Some(v as i64))
+        }
+        Some(_) => Err(cast_error(kind, position)),
+        None => Ok(None),
+    }
+}
+
+fn to_datetime(value: &Data, kind: Kind, position: (u32, u32)) -> PyResult<Option<NaiveDateTime>> {
+    match CellValue::from(value) {
+        CellValue::DateTime(v) => Ok(Some(v)),
+        CellValue::Date(v) => Ok(v.and_hms_opt(0, 0, 0)),
+        CellValue::String(v) if v.trim().is_empty() => Ok(None),
+        CellValue::Empty | CellValue::Error(_) => Ok(None),
+        _ => Err(cast_error(kind, position)),
+    }
+}
+
+/// Builds array from `columns` (positions in range) of the first `nrows` rows.
+/// If `dtype` is omitted, columns of dates are `datetime64[us]` and others are `float64`,
+/// columns of different types are returned as structured array with fields named by
+/// column letters. Missing integers are masked (`numpy.ma.MaskedArray`).
+pub fn range_to_numpy<'py>(
+    py: Python<'py>,
+    range: &Range<Data>,
+    columns: &[usize],
+    nrows: Option<usize>,
+    dtype: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let numpy = py.import_bound("numpy")?;
+    let rows: Vec<&[Data]> = range.rows().take(nrows.unwrap_or(usize::MAX)).collect();
+    let start = range.start().unwrap_or_default();
+
+    let kinds = match dtype {
+        Some(dtype) => vec![Kind::from_dtype(&numpy, dtype)?; columns.len()],
+        None => columns.iter().map(|&col| Kind::infer(&rows, col)).collect(),
+    };
+    let row_size: usize = kinds.iter().map(|kind| kind.itemsize()).sum();
+
+    let data = numpy
+        .getattr("empty")?
+        .call1((rows.len() * row_size, "uint8"))?;
+    let buffer = PyBuffer::<u8>::get_bound(&data)?;
+    let bytes = buffer
+        .as_mut_slice(py)
+        .ok_or_else(|| PyValueError::new_err("numpy.empty returned non-writable array"))?;
+    let mut mask = Vec::with_capacity(rows.len() * columns.len());
+    // without columns there is nothing to write, `max` only avoids zero chunk size
+    for (i, (row, bytes)) in rows
+        .iter()
+        .zip(bytes.chunks_exact(row_size.max(1)))
+        .enumerate()
+    {
+        let mut offset = 0;
+        for (kind, &col) in kinds.iter().zip(columns) {
+            let position = (start.0 + i as u32, start.1 + col as u32);
+            let item = &bytes[offset..offset + kind.itemsize()];
+            mask.push(kind.write(&row[col], position, item)?);
+            offset += kind.itemsize();
+        }
+    }
+    drop(buffer);
+
+    let first = kinds.first().copied().unwrap_or(Kind::Float64);
+    if kinds.iter().all(|&kind| kind == first) {
+        let array = data
+            .call_method1("view", (first.dtype(),))?
+            .call_method1("reshape", ((rows.len(), columns.len()),))?;
+        if !mask.contains(&true) {
+            return Ok(array);
+        }
+        let mask = numpy
+            .getattr("array")?
+            .call1((PyList::new_bound(py, mask),))?
+            .call_method1("reshape", ((rows.len(), columns.len()),))?;
+        return numpy
+            .getattr("ma")?
+            .getattr("masked_array")?
+            .call1((array, mask));
+    }
+
+    // repeated columns get suffixes (`C`, `C.1`, ...), numpy requires unique names
+    let mut repeats = HashMap::new();
+    let fields = kinds
+        .iter()
+        .zip(columns)
+        .map(|(kind, &col)| {
+            let repeat = repeats.entry(col).or_insert(0);
+            let name = match *repeat {
+                0 => column_name(start.1 + col as u32),
+                n => format!("{}.{}", column_name(start.1 + col as u32), n),
+            };
+            *repeat += 1;
+            PyTuple::new_bound(py, [name.into_py(py), kind.dtype().into_py(py)])
+        })
+        .collect::<Vec<_>>();
+    data.call_method1("view", (PyList::new_bound(py, fields),))
+}
@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use calamine::{Data, Range};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+use crate::utils::header_names;
+use crate::{CellValue, ConvertOptions};
+
+/// Type of records returned by `to_records`/`iter_records`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RecordType {
+    #[default]
+    Dict,
+    NamedTuple,
+}
+
+impl<'py> FromPyObject<'py> for RecordType {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "dict" => Ok(RecordType::Dict),
+            "namedtuple" => Ok(RecordType::NamedTuple),
+            other => Err(PyValueError::new_err(format!(
+                "record_type must be one of 'dict' or 'namedtuple', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Indexes of header rows, from `int` or list of `int`.
+pub struct HeaderRows(pub Vec<usize>);
+
+impl<'py> FromPyObject<'py> for HeaderRows {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let rows = match ob.extract::<usize>() {
+            Ok(row) => vec![row],
+            Err(_) => ob.extract::<Vec<usize>>()?,
+        };
+        if rows.is_empty() {
+            return Err(PyValueError::new_err("header_row must not be empty"));
+        }
+        Ok(HeaderRows(rows))
+    }
+}
+
+/// Makes records from rows of range, column names are taken from header rows.
+pub struct RecordBuilder {
+    /// Positions of columns in range
+    columns: Vec<usize>,
+    names: Vec<PyObject>,
+    /// Class of named tuple, `None` for dicts
+    factory: Option<PyObject>,
+    options: ConvertOptions,
+    /// Index of the first row after header (relative to range start)
+    data_row: usize,
+}
+
+impl RecordBuilder {
+    pub fn new(
+        py: Python<'_>,
+        range: &Range<Data>,
+        header_rows: &HeaderRows,
+        columns: Vec<usize>,
+        record_type: RecordType,
+        options: ConvertOptions,
+    ) -> PyResult<Self> {
+        let header: Vec<&[Data]> = header_rows
+            .0
+            .iter()
+            .filter_map(|&row| range.rows().nth(row))
+            .collect();
+        let all_names = header_names(&header, range.width());
+        let names: Vec<&str> = columns.iter().map(|&col| all_names[col].as_str()).collect();
+
+        let factory = match record_type {
+            RecordType::Dict => None,
+            RecordType::NamedTuple => {
+                let kwargs = PyDict::new_bound(py);
+                // invalid identifiers (e.g. `Unnamed: 0`) are replaced with `_0`
+                kwargs.set_item("rename", true)?;
+                let namedtuple = py.import_bound("collections")?.getattr("namedtuple")?;
+                Some(
+                    namedtuple
+                        .call(("Record", names.clone()), Some(&kwargs))?
+                        .unbind(),
+                )
+            }
+        };
+
+        Ok(RecordBuilder {
+            columns,
+            names: names.into_iter().map(|name| name.into_py(py)).collect(),
+            factory,
+            options,
+            data_row: header_rows.0.iter().max().map_or(0, |&row| row + 1),
+        })
+    }
+
+    pub fn data_row(&self) -> usize {
+        self.data_row
+    }
+
+    /// Makes record from `row`, `position` is absolute position of its first cell.
+    pub fn build(&self, py: Python<'_>, row: &[Data], position: (u32, u32)) -> PyResult<PyObject> {
+        let values = self
+            .columns
+            .iter()
+            .map(|&col| {
+                CellValue::convert(&row[col], self.options.dates).to_object_with(
+                    py,
+                    &self.options,
+                    (position.0, position.1 + col as u32),
+                )
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        match &self.factory {
+            Some(factory) => factory.call1(py, PyTuple::new_bound(py, values)),
+            None => {
+                let record = PyDict::new_bound(py);
+                for (name, value) in self.names.iter().zip(values) {
+                    record.set_item(name, value)?;
+                }
+                Ok(record.into_py(py))
+            }
+        }
+    }
+
+    pub fn build_all<'py>(
+        &self,
+        py: Python<'py>,
+        range: &Range<Data>,
+        nrows: Option<usize>,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let start = range.start().unwrap_or_default();
+        let records = range
+            .rows()
+            .enumerate()
+            .skip(self.data_row)
+            .take(nrows.unwrap_or(usize::MAX))
+            .map(|(i, row)| self.build(py, row, (start.0 + i as u32, start.1)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyList::new_bound(py, records))
+    }
+}
+
+/// Makes one list per column of `range` (`columns` are positions in range). With `header_rows`
+/// columns are returned as dict keyed by column names and header rows are not included in lists.
+pub fn build_columns<'py>(
+    py: Python<'py>,
+    range: &Range<Data>,
+    columns: &[usize],
+    header_rows: Option<&HeaderRows>,
+    nrows: Option<usize>,
+    options: &ConvertOptions,
+) -> PyResult<Bound<'py, PyAny>> {
+    let data_row = header_rows
+        .and_then(|rows| rows.0.iter().max())
+        .map_or(0, |&row| row + 1);
+    let rows: Vec<&[Data]> = range
+        .rows()
+        .skip(data_row)
+        .take(nrows.unwrap_or(usize::MAX))
+        .collect();
+    let start = range.start().unwrap_or_default();
+
+    let lists = columns
+        .iter()
+        .map(|&col| {
+            let values = rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    CellValue::convert(&row[col], options.dates).to_object_with(
+                        py,
+                        options,
+                        (start.0 + (data_row + i) as u32, start.1 + col as u32),
+                    )
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new_bound(py, values))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    match header_rows {
+        Some(header_rows) => {
+            let header: Vec<&[Data]> = header_rows
+                .0
+                .iter()
+                .filter_map(|&row| range.rows().nth(row))
+                .collect();
+            let names = header_names(&header, range.width());
+            let result = PyDict::new_bound(py);
+            for (&col, list) in columns.iter().zip(lists) {
+                result.set_item(&names[col], list)?;
+            }
+            Ok(result.into_any())
+        }
+        None => Ok(PyList::new_bound(py, lists).into_any()),
+    }
+}
+
+#[pyclass]
+pub struct CalamineRecordIterator {
+    builder: RecordBuilder,
+    range: Arc<Range<Data>>,
+    /// Index of the next row (relative to range start)
+    row: usize,
+    /// Index of the row after the last one
+    end: usize,
+}
+
+impl CalamineRecordIterator {
+    pub fn new(builder: RecordBuilder, range: Arc<Range<Data>>, nrows: Option<usize>) -> Self {
+        let row = builder.data_row();
+        let end = match nrows {
+            Some(nrows) => range.height().min(row.saturating_add(nrows)),
+            None => range.height(),
+        };
+        CalamineRecordIterator {
+            builder,
+            range,
+            row,
+            end,
+        }
+    }
+}
+
+#[pymethods]
+impl CalamineRecordIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<PyObject>> {
+        if slf.row >= slf.end {
+            return Ok(None);
+        }
+        let index = slf.row;
+        slf.row += 1;
+
+        let start = slf.range.start().unwrap_or_default();
+        let position = (start.0 + index as u32, start.1);
+        // `index < end <= height`, rows are indexed directly instead of iterating
+        let row = &slf.range[index];
+        slf.builder.build(slf.py(), row, position).map(Some)
+    }
+}
@@ -1,12 +1,25 @@
 use std::fmt::Display;
 use std::sync::Arc;
 
-use calamine::{Data, Range, Rows, SheetType, SheetVisible};
+use calamine::{Data, Dimensions, Range, Rows, SheetType, SheetVisible};
 use pyo3::class::basic::CompareOp;
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyCapsule, PyList, PyString};
 
-use crate::CellValue;
+use crate::types::arrow::{range_to_record_batch, to_stream_capsule};
+use crate::types::numpy::range_to_numpy;
+use crate::types::records::{
+    build_columns, CalamineRecordIterator, HeaderRows, RecordBuilder, RecordType,
+};
+use crate::types::skiprows::{RowFilter, SkipRows};
+use crate::types::usecols::{selected_columns, selected_data_columns, UseCols};
+use crate::utils::{arrow_err_to_py, parse_area, parse_cell, Area};
+use crate::xlsx::NumberFormats;
+use crate::{
+    CalamineArrowTable, CalamineError, CellValue, ConvertOptions, DatesMode, EmptyValueArg,
+    ErrorsMode, FloatsMode, NumbersMode, ValuesMode,
+};
 
 #[pyclass(eq, eq_int)]
 #[derive(Clone, Debug, PartialEq)]
@@ -115,11 +128,22 @@ impl SheetMetadata {
     }
 }
 
+/// Merged region as `((start_row, start_col), (end_row, end_col))`.
+type MergedRegion = ((u32, u32), (u32, u32));
+
 #[pyclass]
 pub struct CalamineSheet {
     #[pyo3(get)]
     name: String,
     range: Arc<Range<Data>>,
+    formulas: Option<Arc<Range<String>>>,
+    number_formats: Option<Arc<NumberFormats>>,
+    /// `None` if not loaded, `Some(None)` if format doesn't support merged cells.
+    merged_cells: Option<Option<Vec<Dimensions>>>,
+    empty_value: Option<PyObject>,
+    dates: DatesMode,
+    /// Absolute bounds of sub-range, which shares `range` with the sheet.
+    window: Option<Dimensions>,
 }
 
 impl CalamineSheet {
@@ -127,8 +151,185 @@ impl CalamineSheet {
         CalamineSheet {
             name,
             range: Arc::new(range),
+            formulas: None,
+            number_formats: None,
+            merged_cells: None,
+            empty_value: None,
+            dates: DatesMode::Infer,
+            window: None,
         }
     }
+
+    pub fn with_merged_cells(mut self, merged_cells: Option<Vec<Dimensions>>) -> Self {
+        self.merged_cells = Some(merged_cells);
+        self
+    }
+
+    pub fn with_formulas(mut self, formulas: Range<String>) -> Self {
+        self.formulas = Some(Arc::new(formulas));
+        self
+    }
+
+    pub fn with_number_formats(mut self, number_formats: NumberFormats) -> Self {
+        self.number_formats = Some(Arc::new(number_formats));
+        self
+    }
+
+    pub fn with_empty_value(mut self, empty_value: Option<PyObject>) -> Self {
+        self.empty_value = empty_value;
+        self
+    }
+
+    pub fn with_dates(mut self, dates: DatesMode) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    fn convert_options(
+        &self,
+        py: Python<'_>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        dates: Option<DatesMode>,
+    ) -> ConvertOptions {
+        ConvertOptions::new(py, errors, empty_value, self.empty_value.as_ref())
+            .with_dates(dates.unwrap_or(self.dates))
+    }
+
+    /// Options for `to_python`/`iter_rows`, formatted values need loaded number formats.
+    fn rows_options(
+        &self,
+        py: Python<'_>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        dates: Option<DatesMode>,
+        values: ValuesMode,
+    ) -> PyResult<ConvertOptions> {
+        let number_formats = match values {
+            ValuesMode::Typed => None,
+            ValuesMode::Formatted => Some(Arc::clone(self.loaded_number_formats()?)),
+        };
+        Ok(self
+            .convert_options(py, errors, empty_value, dates)
+            .with_number_formats(number_formats))
+    }
+
+    fn loaded_number_formats(&self) -> PyResult<&Arc<NumberFormats>> {
+        self.number_formats.as_ref().ok_or_else(|| {
+            CalamineError::new_err(
+                "Number formats are not loaded, use get_sheet_by_name(name, number_formats=True)",
+            )
+        })
+    }
+
+    fn loaded_merged_cells(&self) -> PyResult<&Option<Vec<Dimensions>>> {
+        self.merged_cells.as_ref().ok_or_else(|| {
+            CalamineError::new_err(
+                "Merged cells are not loaded, use get_sheet_by_name(name, merged_cells=True)",
+            )
+        })
+    }
+
+    /// Absolute (start, end) of the sheet or its sub-range, `None` if empty.
+    fn bounds(&self) -> Option<Area> {
+        match self.window {
+            Some(window) => Some((window.start, window.end)),
+            None => self.range.start().zip(self.range.end()),
+        }
+    }
+
+    /// Converts position counted from `start` to absolute position if `relative`.
+    fn absolute_position(&self, position: (u32, u32), relative: bool) -> (u32, u32) {
+        if !relative {
+            return position;
+        }
+        let start = self.bounds().map(|bounds| bounds.0).unwrap_or_default();
+        (
+            start.0.saturating_add(position.0),
+            start.1.saturating_add(position.1),
+        )
+    }
+
+    fn cell_value(&self, py: Python<'_>, position: (u32, u32)) -> PyResult<PyObject> {
+        if let Some(window) = self.window {
+            if !window.contains(position.0, position.1) {
+                return Err(PyIndexError::new_err(format!(
+                    "Cell ({}, {}) is out of range",
+                    position.0, position.1
+                )));
+            }
+        }
+        let options = self.convert_options(py, ErrorsMode::Value, EmptyValueArg::Default, None);
+        self.range
+            .get_value(position)
+            .map_or(CellValue::Empty, |value| {
+                CellValue::convert(value, options.dates)
+            })
+            .to_object_with(py, &options, position)
+    }
+
+    /// Returns the sheet range limited by sub-range,
+    /// with merged regions filled by top-left value if `fill_merged`.
+    fn data_range(&self, fill_merged: bool) -> PyResult<Arc<Range<Data>>> {
+        let range = self.filled_range(fill_merged)?;
+        Ok(match self.window {
+            Some(window) if !range.is_empty() => Arc::new(range.range(window.start, window.end)),
+            _ => range,
+        })
+    }
+
+    fn filled_range(&self, fill_merged: bool) -> PyResult<Arc<Range<Data>>> {
+        if !fill_merged {
+            return Ok(Arc::clone(&self.range));
+        }
+
+        let merged_cells = self.loaded_merged_cells()?.as_ref().ok_or_else(|| {
+            CalamineError::new_err("Merged cells are not supported for this format")
+        })?;
+
+        let mut range = (*self.range).clone();
+        let end = range.end().unwrap_or_default();
+        for region in merged_cells {
+            let Some(value) = self.range.get_value(region.start) else {
+                continue;
+            };
+            // regions are clipped by the sheet range, so its size isn't changed
+            for row in region.start.0..=region.end.0.min(end.0) {
+                for col in region.start.1..=region.end.1.min(end.1) {
+                    if (row, col) != region.start {
+                        range.set_value((row, col), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(Arc::new(range))
+    }
+
+    /// Returns the part of `range` which `to_python` reads and the number of rows to take from it.
+    fn rows_range(
+        range: &Arc<Range<Data>>,
+        skip_empty_area: bool,
+        nrows: Option<u32>,
+    ) -> (Arc<Range<Data>>, u32) {
+        let nrows = match nrows {
+            Some(nrows) => nrows,
+            None => range.end().map_or(0, |end| end.0 + 1),
+        };
+
+        let range = if skip_empty_area || Some((0, 0)) == range.start() {
+            Arc::clone(range)
+        } else if let Some(end) = range.end() {
+            Arc::new(range.range(
+                (0, 0),
+                (if nrows > end.0 { end.0 } else { nrows - 1 }, end.1),
+            ))
+        } else {
+            Arc::clone(range)
+        };
+
+        (range, nrows)
+    }
 }
 
 #[pymethods]
@@ -139,88 +340,522 @@ impl CalamineSheet {
 
     #[getter]
     fn height(&self) -> usize {
-        self.range.height()
+        self.bounds()
+            .map_or(0, |(start, end)| (end.0 - start.0 + 1) as usize)
     }
 
     #[getter]
     fn width(&self) -> usize {
-        self.range.width()
+        self.bounds()
+            .map_or(0, |(start, end)| (end.1 - start.1 + 1) as usize)
     }
 
     #[getter]
     fn total_height(&self) -> u32 {
-        self.range.end().unwrap_or_default().0
+        self.end().unwrap_or_default().0
     }
 
     #[getter]
     fn total_width(&self) -> u32 {
-        self.range.end().unwrap_or_default().1
+        self.end().unwrap_or_default().1
     }
 
     #[getter]
     fn start(&self) -> Option<(u32, u32)> {
-        self.range.start()
+        self.bounds().map(|bounds| bounds.0)
     }
 
     #[getter]
     fn end(&self) -> Option<(u32, u32)> {
-        self.range.end()
+        self.bounds().map(|bounds| bounds.1)
     }
 
-    #[pyo3(signature = (skip_empty_area=true, nrows=None))]
+    #[pyo3(signature = (row, col, relative=false))]
+    fn cell(&self, py: Python<'_>, row: u32, col: u32, relative: bool) -> PyResult<PyObject> {
+        self.cell_value(py, self.absolute_position((row, col), relative))
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let position = match key.downcast::<PyString>() {
+            Ok(reference) => {
+                let reference = reference.to_str()?;
+                parse_cell(reference).ok_or_else(|| {
+                    PyValueError::new_err(format!("Invalid cell reference '{}'", reference))
+                })?
+            }
+            Err(_) => key.extract::<(u32, u32)>()?,
+        };
+        self.cell_value(py, position)
+    }
+
+    #[pyo3(signature = (start, end=None, relative=false))]
+    fn range(
+        &self,
+        py: Python<'_>,
+        start: &Bound<'_, PyAny>,
+        end: Option<(u32, u32)>,
+        relative: bool,
+    ) -> PyResult<CalamineSheet> {
+        let (start, end) = match (start.downcast::<PyString>(), end) {
+            (Ok(reference), None) => {
+                let reference = reference.to_str()?;
+                parse_area(reference).ok_or_else(|| {
+                    PyValueError::new_err(format!("Invalid range reference '{}'", reference))
+                })?
+            }
+            (Err(_), Some(end)) => (start.extract::<(u32, u32)>()?, end),
+            _ => {
+                return Err(PyTypeError::new_err(
+                    "range() takes reference like 'A1:D20' or start and end (row, col) tuples",
+                ))
+            }
+        };
+        if start.0 > end.0 || start.1 > end.1 {
+            return Err(PyValueError::new_err(format!(
+                "Range start {:?} is after end {:?}",
+                start, end
+            )));
+        }
+
+        let start = self.absolute_position(start, relative);
+        let end = self.absolute_position(end, relative);
+        // whole rows and columns are limited by data of sheet
+        let (data_start, data_end) = self.bounds().unwrap_or_default();
+        let (start, end) = match (end.0 == u32::MAX, end.1 == u32::MAX) {
+            (true, _) => (
+                (start.0.max(data_start.0), start.1),
+                (data_end.0.max(start.0), end.1),
+            ),
+            (_, true) => (
+                (start.0, start.1.max(data_start.1)),
+                (end.0, data_end.1.max(start.1)),
+            ),
+            _ => (start, end),
+        };
+        if let Some(window) = self.window {
+            if !window.contains(start.0, start.1) || !window.contains(end.0, end.1) {
+                return Err(PyIndexError::new_err(format!(
+                    "Range {:?}..{:?} is out of range",
+                    start, end
+                )));
+            }
+        }
+        // window is clipped by data, so huge ranges aren't allocated
+        let window = match (self.range.start(), self.range.end()) {
+            (Some(data_start), Some(data_end)) => {
+                let start = (start.0.max(data_start.0), start.1.max(data_start.1));
+                let end = (end.0.min(data_end.0), end.1.min(data_end.1));
+                Some(Dimensions::new(start, end)).filter(|_| start.0 <= end.0 && start.1 <= end.1)
+            }
+            _ => None,
+        };
+        let Some(window) = window else {
+            return Ok(CalamineSheet::new(self.name.clone(), Range::empty())
+                .with_empty_value(self.empty_value.as_ref().map(|v| v.clone_ref(py)))
+                .with_dates(self.dates));
+        };
+
+        Ok(CalamineSheet {
+            name: self.name.clone(),
+            range: Arc::clone(&self.range),
+            formulas: self.formulas.clone(),
+            number_formats: self.number_formats.clone(),
+            merged_cells: self.merged_cells.clone(),
+            empty_value: self.empty_value.as_ref().map(|v| v.clone_ref(py)),
+            dates: self.dates,
+            window: Some(window),
+        })
+    }
+
+    #[getter]
+    fn merged_cells(&self) -> PyResult<Option<Vec<MergedRegion>>> {
+        Ok(self.loaded_merged_cells()?.as_ref().map(|merged_cells| {
+            merged_cells
+                .iter()
+                .map(|region| (region.start, region.end))
+                .collect()
+        }))
+    }
+
+    #[pyo3(signature = (
+        skip_empty_area=true,
+        nrows=None,
+        errors=ErrorsMode::Value,
+        empty_value=EmptyValueArg::Default,
+        fill_merged=false,
+        usecols=None,
+        skiprows=None,
+        skipfooter=0,
+        skip_blank_rows=false,
+        dates=None,
+        values=ValuesMode::Typed,
+        floats=FloatsMode::Float,
+        numbers=NumbersMode::Keep,
+    ))]
+    #[allow(clippy::too_many_arguments)]
     fn to_python(
         slf: PyRef<'_, Self>,
         skip_empty_area: bool,
         nrows: Option<u32>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        fill_merged: bool,
+        usecols: Option<UseCols>,
+        skiprows: Option<SkipRows>,
+        skipfooter: usize,
+        skip_blank_rows: bool,
+        dates: Option<DatesMode>,
+        values: ValuesMode,
+        floats: FloatsMode,
+        numbers: NumbersMode,
     ) -> PyResult<Bound<'_, PyList>> {
-        let nrows = match nrows {
-            Some(nrows) => nrows,
-            None => slf.range.end().map_or(0, |end| end.0 + 1),
+        let py = slf.py();
+        let options = slf
+            .rows_options(py, errors, empty_value, dates, values)?
+            .with_floats(floats)
+            .with_numbers(numbers);
+        let filter = RowFilter::new(skiprows, skipfooter, skip_blank_rows);
+        let data = slf.data_range(fill_merged)?;
+        // with skipped rows `nrows` is counted after skipping, so range isn't truncated
+        let (range, limit) = if filter.is_noop() {
+            Self::rows_range(&data, skip_empty_area, nrows)
+        } else {
+            Self::rows_range(&data, skip_empty_area, None)
         };
+        let nrows = nrows.unwrap_or(limit);
+        let columns = usecols
+            .map(|usecols| usecols.data_positions(&range, &data))
+            .transpose()?;
 
-        let range = if skip_empty_area || Some((0, 0)) == slf.range.start() {
-            Arc::clone(&slf.range)
-        } else if let Some(end) = slf.range.end() {
-            Arc::new(slf.range.range(
-                (0, 0),
-                (if nrows > end.0 { end.0 } else { nrows - 1 }, end.1),
-            ))
-        } else {
-            Arc::clone(&slf.range)
+        let start = range.start().unwrap_or_default();
+        let height = range.height();
+        let mut rows = Vec::new();
+        for (i, row) in range.rows().enumerate() {
+            if rows.len() >= nrows as usize {
+                break;
+            }
+            if filter.skip(py, i, height, row, columns.as_deref())? {
+                continue;
+            }
+            rows.push(row_to_py(
+                py,
+                row,
+                (start.0 + i as u32, start.1),
+                columns.as_deref(),
+                &options,
+            )?);
+        }
+
+        Ok(PyList::new_bound(slf.py(), rows))
+    }
+
+    #[pyo3(signature = (
+        header_row=HeaderRows(vec![0]),
+        skip_empty_area=true,
+        nrows=None,
+        errors=ErrorsMode::Value,
+        empty_value=EmptyValueArg::Default,
+        fill_merged=false,
+        record_type=RecordType::Dict,
+        usecols=None,
+        dates=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_records<'py>(
+        &self,
+        py: Python<'py>,
+        header_row: HeaderRows,
+        skip_empty_area: bool,
+        nrows: Option<usize>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        fill_merged: bool,
+        record_type: RecordType,
+        usecols: Option<UseCols>,
+        dates: Option<DatesMode>,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let options = self.convert_options(py, errors, empty_value, dates);
+        let (range, _) = Self::rows_range(&self.data_range(fill_merged)?, skip_empty_area, None);
+        let columns = selected_columns(usecols.as_ref(), &range, &header_row.0)?;
+        RecordBuilder::new(py, &range, &header_row, columns, record_type, options)?
+            .build_all(py, &range, nrows)
+    }
+
+    #[pyo3(signature = (
+        header_row=HeaderRows(vec![0]),
+        skip_empty_area=true,
+        nrows=None,
+        errors=ErrorsMode::Value,
+        empty_value=EmptyValueArg::Default,
+        fill_merged=false,
+        record_type=RecordType::Dict,
+        usecols=None,
+        dates=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn iter_records(
+        &self,
+        py: Python<'_>,
+        header_row: HeaderRows,
+        skip_empty_area: bool,
+        nrows: Option<usize>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        fill_merged: bool,
+        record_type: RecordType,
+        usecols: Option<UseCols>,
+        dates: Option<DatesMode>,
+    ) -> PyResult<CalamineRecordIterator> {
+        let options = self.convert_options(py, errors, empty_value, dates);
+        let (range, _) = Self::rows_range(&self.data_range(fill_merged)?, skip_empty_area, None);
+        let columns = selected_columns(usecols.as_ref(), &range, &header_row.0)?;
+        let builder = RecordBuilder::new(py, &range, &header_row, columns, record_type, options)?;
+        Ok(CalamineRecordIterator::new(builder, range, nrows))
+    }
+
+    #[pyo3(signature = (
+        header_row=None,
+        skip_empty_area=true,
+        nrows=None,
+        errors=ErrorsMode::Value,
+        empty_value=EmptyValueArg::Default,
+        fill_merged=false,
+        usecols=None,
+        dates=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_columns<'py>(
+        &self,
+        py: Python<'py>,
+        header_row: Option<HeaderRows>,
+        skip_empty_area: bool,
+        nrows: Option<usize>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        fill_merged: bool,
+        usecols: Option<UseCols>,
+        dates: Option<DatesMode>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let options = self.convert_options(py, errors, empty_value, dates);
+        let (range, _) = Self::rows_range(&self.data_range(fill_merged)?, skip_empty_area, None);
+        let header_rows = header_row.as_ref().map_or(&[0][..], |rows| &rows.0);
+        let columns = selected_columns(usecols.as_ref(), &range, header_rows)?;
+        build_columns(py, &range, &columns, header_row.as_ref(), nrows, &options)
+    }
+
+    #[pyo3(signature = (
+        dtype=None,
+        usecols=None,
+        skip_empty_area=true,
+        nrows=None,
+        fill_merged=false,
+    ))]
+    fn to_numpy<'py>(
+        &self,
+        py: Python<'py>,
+        dtype: Option<&Bound<'py, PyAny>>,
+        usecols: Option<UseCols>,
+        skip_empty_area: bool,
+        nrows: Option<usize>,
+        fill_merged: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let data = self.data_range(fill_merged)?;
+        let (range, _) = Self::rows_range(&data, skip_empty_area, None);
+        let columns = selected_data_columns(usecols.as_ref(), &range, &data)?;
+        range_to_numpy(py, &range, &columns, nrows, dtype)
+    }
+
+    #[pyo3(signature = (skip_empty_area=true, nrows=None, usecols=None))]
+    fn formulas(
+        slf: PyRef<'_, Self>,
+        skip_empty_area: bool,
+        nrows: Option<u32>,
+        usecols: Option<UseCols>,
+    ) -> PyResult<Bound<'_, PyList>> {
+        let formulas = slf.formulas.as_ref().ok_or_else(|| {
+            CalamineError::new_err(
+                "Formulas are not loaded, use get_sheet_by_name(name, formulas=True)",
+            )
+        })?;
+        let data = slf.data_range(false)?;
+        let (range, nrows) = Self::rows_range(&data, skip_empty_area, nrows);
+        let columns = selected_data_columns(usecols.as_ref(), &range, &data)?;
+
+        let (start, end) = match (range.start(), range.end()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Ok(PyList::empty_bound(slf.py())),
         };
+        let rows = (start.0..end.0 + 1).take(nrows as usize).map(|row| {
+            let cells = columns.iter().map(|&col| {
+                let col = start.1 + col as u32;
+                formulas
+                    .get_value((row, col))
+                    .filter(|formula| !formula.is_empty())
+            });
+            PyList::new_bound(slf.py(), cells)
+        });
 
-        Ok(PyList::new_bound(
-            slf.py(),
-            range.rows().take(nrows as usize).map(|row| {
-                PyList::new_bound(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))
-            }),
-        ))
+        Ok(PyList::new_bound(slf.py(), rows))
+    }
+
+    #[pyo3(signature = (skip_empty_area=true, nrows=None, usecols=None))]
+    fn number_formats(
+        slf: PyRef<'_, Self>,
+        skip_empty_area: bool,
+        nrows: Option<u32>,
+        usecols: Option<UseCols>,
+    ) -> PyResult<Bound<'_, PyList>> {
+        let number_formats = slf.loaded_number_formats()?;
+        let data = slf.data_range(false)?;
+        let (range, nrows) = Self::rows_range(&data, skip_empty_area, nrows);
+        let columns = selected_data_columns(usecols.as_ref(), &range, &data)?;
+
+        let (start, end) = match (range.start(), range.end()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Ok(PyList::empty_bound(slf.py())),
+        };
+        let rows = (start.0..end.0 + 1).take(nrows as usize).map(|row| {
+            let cells = columns.iter().map(|&col| {
+                let col = start.1 + col as u32;
+                number_formats.get((row, col)).unwrap_or("General")
+            });
+            PyList::new_bound(slf.py(), cells)
+        });
+
+        Ok(PyList::new_bound(slf.py(), rows))
+    }
+
+    #[pyo3(signature = (header_row=Some(0), skip_empty_area=true, usecols=None))]
+    fn to_arrow(
+        &self,
+        py: Python<'_>,
+        header_row: Option<usize>,
+        skip_empty_area: bool,
+        usecols: Option<UseCols>,
+    ) -> PyResult<CalamineArrowTable> {
+        let (range, _) = Self::rows_range(&self.data_range(false)?, skip_empty_area, None);
+        let header_rows = header_row.as_slice();
+        let columns = selected_columns(usecols.as_ref(), &range, header_rows)?;
+        py.allow_threads(|| range_to_record_batch(&range, &columns, header_row, self.dates))
+            .map(CalamineArrowTable::new)
+            .map_err(arrow_err_to_py)
     }
 
-    fn iter_rows(&self) -> CalamineCellIterator {
-        CalamineCellIterator::from_range(Arc::clone(&self.range))
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // requested schema is optional for producers and isn't supported
+        let _ = requested_schema;
+        let range = self.data_range(false)?;
+        let columns: Vec<usize> = (0..range.width()).collect();
+        let batch = py
+            .allow_threads(|| range_to_record_batch(&range, &columns, Some(0), self.dates))
+            .map_err(arrow_err_to_py)?;
+        to_stream_capsule(py, batch)
+    }
+
+    #[pyo3(signature = (
+        errors=ErrorsMode::Value,
+        empty_value=EmptyValueArg::Default,
+        fill_merged=false,
+        usecols=None,
+        skiprows=None,
+        skipfooter=0,
+        skip_blank_rows=false,
+        dates=None,
+        values=ValuesMode::Typed,
+        floats=FloatsMode::Float,
+        numbers=NumbersMode::Keep,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn iter_rows(
+        &self,
+        py: Python<'_>,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        fill_merged: bool,
+        usecols: Option<UseCols>,
+        skiprows: Option<SkipRows>,
+        skipfooter: usize,
+        skip_blank_rows: bool,
+        dates: Option<DatesMode>,
+        values: ValuesMode,
+        floats: FloatsMode,
+        numbers: NumbersMode,
+    ) -> PyResult<CalamineCellIterator> {
+        let options = self
+            .rows_options(py, errors, empty_value, dates, values)?
+            .with_floats(floats)
+            .with_numbers(numbers);
+        let range = self.data_range(fill_merged)?;
+        let columns = usecols
+            .map(|usecols| usecols.positions(&range, &[0]))
+            .transpose()?;
+        let filter = RowFilter::new(skiprows, skipfooter, skip_blank_rows);
+        Ok(CalamineCellIterator::from_range(
+            range, columns, filter, options,
+        ))
     }
 }
 
+/// Converts `row` to list, `columns` are positions of cells to take (all cells, if `None`).
+pub fn row_to_py<'py>(
+    py: Python<'py>,
+    row: &[Data],
+    start: (u32, u32),
+    columns: Option<&[usize]>,
+    options: &ConvertOptions,
+) -> PyResult<Bound<'py, PyList>> {
+    let convert = |i: usize| options.to_object(py, &row[i], (start.0, start.1 + i as u32));
+    let cells = match columns {
+        Some(columns) => columns.iter().map(|&i| convert(i)).collect(),
+        None => (0..row.len()).map(convert).collect::<PyResult<Vec<_>>>(),
+    }?;
+
+    Ok(PyList::new_bound(py, cells))
+}
+
 #[pyclass]
 pub struct CalamineCellIterator {
     position: u32,
     start: (u32, u32),
-    empty_row: Vec<CellValue>,
+    width: usize,
+    /// Positions of selected columns, all columns if `None`
+    columns: Option<Vec<usize>>,
+    filter: RowFilter,
+    /// Number of rows from the top of sheet to the end of data
+    height: usize,
+    /// Cells of empty rows before data, checked by `filter`
+    empty_cells: Vec<Data>,
+    options: ConvertOptions,
     iter: Rows<'static, Data>,
     #[allow(dead_code)]
     range: Arc<Range<Data>>,
 }
 
 impl CalamineCellIterator {
-    fn from_range(range: Arc<Range<Data>>) -> CalamineCellIterator {
-        let empty_row = (0..range.width())
-            .map(|_| CellValue::String("".to_string()))
-            .collect();
+    fn from_range(
+        range: Arc<Range<Data>>,
+        columns: Option<Vec<usize>>,
+        filter: RowFilter,
+        options: ConvertOptions,
+    ) -> CalamineCellIterator {
+        // empty range has no start, it yields no rows
+        let start = range.start().unwrap_or_default();
         CalamineCellIterator {
-            empty_row,
+            width: columns
+                .as_ref()
+                .map_or(range.width(), |columns| columns.len()),
+            columns,
+            filter,
+            height: start.0 as usize + range.height(),
+            empty_cells: vec![Data::Empty; range.width()],
+            options,
             position: 0,
-            start: range.start().unwrap(),
+            start,
             iter: unsafe {
                 std::mem::transmute::<
                     calamine::Rows<'_, calamine::Data>,
@@ -238,14 +873,34 @@ impl CalamineCellIterator {
         slf
     }
 
-    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Bound<'_, PyList>> {
-        slf.position += 1;
-        if slf.position > slf.start.0 {
-            slf.iter.next().map(|row| {
-                PyList::new_bound(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))
-            })
-        } else {
-            Some(PyList::new_bound(slf.py(), slf.empty_row.clone()))
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Bound<'_, PyList>>> {
+        let py = slf.py();
+        let slf = &mut *slf;
+        loop {
+            let index = slf.position;
+            slf.position += 1;
+            let columns = slf.columns.as_deref();
+            if index >= slf.start.0 {
+                let Some(row) = slf.iter.next() else {
+                    return Ok(None);
+                };
+                if slf
+                    .filter
+                    .skip(py, index as usize, slf.height, row, columns)?
+                {
+                    continue;
+                }
+                let position = (index, slf.start.1);
+                return row_to_py(py, row, position, columns, &slf.options).map(Some);
+            }
+            if slf
+                .filter
+                .skip(py, index as usize, slf.height, &slf.empty_cells, columns)?
+            {
+                continue;
+            }
+            let empty_row = (0..slf.width).map(|_| slf.options.empty_to_object(py));
+            return Ok(Some(PyList::new_bound(py, empty_row)));
         }
     }
 }
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use calamine::Data;
+use pyo3::prelude::*;
+
+/// Rows skipped by `skiprows`.
+pub enum SkipRows {
+    /// Number of rows at the top
+    Count(usize),
+    /// Zero-based indexes of rows
+    Indexes(HashSet<usize>),
+    /// Called with index of row, row is skipped if result is true
+    Callable(PyObject),
+}
+
+impl<'py> FromPyObject<'py> for SkipRows {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(count) = ob.extract::<usize>() {
+            Ok(SkipRows::Count(count))
+        } else if ob.is_callable() {
+            Ok(SkipRows::Callable(ob.clone().unbind()))
+        } else {
+            Ok(SkipRows::Indexes(
+                ob.extract::<Vec<usize>>()?.into_iter().collect(),
+            ))
+        }
+    }
+}
+
+/// Decides which rows are skipped by `skiprows`, `skipfooter` and `skip_blank_rows`.
+/// Indexes of rows are counted from the first row of returned data.
+pub struct RowFilter {
+    skiprows: Option<SkipRows>,
+    skipfooter: usize,
+    skip_blank_rows: bool,
+}
+
+impl RowFilter {
+    pub fn new(skiprows: Option<SkipRows>, skipfooter: usize, skip_blank_rows: bool) -> Self {
+        RowFilter {
+            skiprows,
+            skipfooter,
+            skip_blank_rows,
+        }
+    }
+
+    /// Returns `true` if no rows are skipped.
+    pub fn is_noop(&self) -> bool {
+        self.skiprows.is_none() && self.skipfooter == 0 && !self.skip_blank_rows
+    }
+
+    /// Returns `true` if row at `index` with cells `row` must be skipped, `height` is
+    /// the number of rows of data. Only `columns` are checked for blank cells, if set.
+    pub fn skip(
+        &self,
+        py: Python<'_>,
+        index: usize,
+        height: usize,
+        row: &[Data],
+        columns: Option<&[usize]>,
+    ) -> PyResult<bool> {
+        if index >= height.saturating_sub(self.skipfooter) {
+            return Ok(true);
+        }
+        if self.skip_blank_rows {
+            let is_blank = |value: &Data| match value {
+                Data::Empty => true,
+                Data::String(s) => s.trim().is_empty(),
+                _ => false,
+            };
+            let blank = match columns {
+                Some(columns) => columns.iter().all(|&col| is_blank(&row[col])),
+                None => row.iter().all(is_blank),
+            };
+            if blank {
+                return Ok(true);
+            }
+        }
+        match &self.skiprows {
+            Some(SkipRows::Count(count)) => Ok(index < *count),
+            Some(SkipRows::Indexes(indexes)) => Ok(indexes.contains(&index)),
+            Some(SkipRows::Callable(callable)) => callable.bind(py).call1((index,))?.is_truthy(),
+            None => Ok(false),
+        }
+    }
+}
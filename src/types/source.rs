@@ -0,0 +1,269 @@
+//! Sources of workbooks read from memory or Python objects.
+use std::fs::File;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use memmap2::Mmap;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// Bytes of workbook, owned, borrowed from Python buffer or memory-mapped file.
+#[derive(Clone)]
+pub enum Bytes {
+    /// Read from file-like object
+    Owned(Arc<[u8]>),
+    /// Read-only buffer of `bytes`, `memoryview`, etc., read without copying.
+    /// The buffer is released when the last clone is dropped.
+    Buffer(Arc<PyBuffer<u8>>),
+    /// Read-only memory map of file, pages are shared with other processes mapping it.
+    Mmap(Arc<Mmap>),
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Bytes::Owned(data) => data,
+            Bytes::Mmap(map) => map,
+            Bytes::Buffer(buffer) if buffer.len_bytes() == 0 => &[],
+            Bytes::Buffer(buffer) => {
+                // SAFETY: the buffer is contiguous, read-only and stays exported
+                // while `self` is alive (see `WorkbookData::from_buffer`).
+                unsafe {
+                    std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes())
+                }
+            }
+        }
+    }
+}
+
+/// Size of parts read from file-like object.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Number of parts kept by reader, zip archives are read jumping between
+/// the central directory at the end of file and the entries.
+const CACHED_CHUNKS: usize = 4;
+
+/// `Read + Seek` over Python file-like object, which reads only requested parts of file.
+/// Like reading the object at once, the workbook starts at the position of the object
+/// when the reader is created.
+/// The GIL is acquired for every read, so the reader works inside `allow_threads`.
+/// Every reader keeps its own position and seeks the object right before reading,
+/// so readers reopened from the same object don't interfere. The position of the object
+/// is restored after reading. Seek and read aren't atomic (`read` of Python files
+/// releases the GIL), so the object must not be used by other threads meanwhile.
+pub struct PyFileReader {
+    file: PyObject,
+    /// Position of the object, where workbook starts
+    start: u64,
+    /// Position relative to `start`
+    position: u64,
+    len: u64,
+    /// The last parts read from file by their index, the most recent is the last
+    chunks: Vec<(u64, Vec<u8>)>,
+}
+
+impl PyFileReader {
+    fn new(file: PyObject) -> PyResult<Self> {
+        Python::with_gil(|py| {
+            let bound = file.bind(py);
+            for method in ["read", "seek"] {
+                if !bound.hasattr(method)? {
+                    return Err(PyTypeError::new_err(format!(
+                        "Object does not have a .{}() method.",
+                        method
+                    )));
+                }
+            }
+            let start: u64 = bound.call_method1("seek", (0, 1))?.extract()?;
+            let end: u64 = bound.call_method1("seek", (0, 2))?.extract()?;
+            bound.call_method1("seek", (start, 0))?;
+            Ok(PyFileReader {
+                file,
+                start,
+                position: 0,
+                len: end.saturating_sub(start),
+                chunks: Vec::new(),
+            })
+        })
+    }
+}
+
+impl Clone for PyFileReader {
+    fn clone(&self) -> Self {
+        Python::with_gil(|py| PyFileReader {
+            file: self.file.clone_ref(py),
+            start: self.start,
+            position: self.position,
+            len: self.len,
+            chunks: Vec::new(),
+        })
+    }
+}
+
+impl PyFileReader {
+    /// Reads `size` bytes at `position` relative to `start`, less only at the end of file.
+    fn read_at(&self, position: u64, size: usize) -> io::Result<Vec<u8>> {
+        Python::with_gil(|py| -> PyResult<Vec<u8>> {
+            let file = self.file.bind(py);
+            let previous = file.call_method1("seek", (0, 1))?;
+            file.call_method1("seek", (self.start + position, 0))?;
+            let mut data = Vec::with_capacity(size);
+            // raw and non-blocking objects may return less than requested
+            while data.len() < size {
+                let part = file.call_method1("read", (size - data.len(),))?;
+                let read = match part.downcast::<PyBytes>() {
+                    Ok(part) => {
+                        data.extend_from_slice(part.as_bytes());
+                        part.as_bytes().len()
+                    }
+                    // `bytearray`, `memoryview` and other buffers
+                    Err(_) => {
+                        let part = PyBuffer::<u8>::get_bound(&part)?.to_vec(py)?;
+                        data.extend_from_slice(&part);
+                        part.len()
+                    }
+                };
+                if read == 0 {
+                    break;
+                }
+            }
+            file.call_method1("seek", (previous, 0))?;
+            Ok(data)
+        })
+        .map_err(io::Error::other)
+    }
+}
+
+impl Read for PyFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let index = self.position / CHUNK_SIZE as u64;
+        let cached = self.chunks.iter().position(|(i, _)| *i == index);
+        if cached.is_none() && buf.len() >= CHUNK_SIZE {
+            let data = self.read_at(self.position, buf.len())?;
+            let read = data.len().min(buf.len());
+            buf[..read].copy_from_slice(&data[..read]);
+            self.position += read as u64;
+            return Ok(read);
+        }
+        let chunk = match cached {
+            Some(cached) => {
+                let chunk = self.chunks.remove(cached);
+                self.chunks.push(chunk);
+                &self.chunks[self.chunks.len() - 1].1
+            }
+            None => {
+                let data = self.read_at(index * CHUNK_SIZE as u64, CHUNK_SIZE)?;
+                if self.chunks.len() == CACHED_CHUNKS {
+                    self.chunks.remove(0);
+                }
+                self.chunks.push((index, data));
+                &self.chunks[self.chunks.len() - 1].1
+            }
+        };
+        let offset = (self.position % CHUNK_SIZE as u64) as usize;
+        if offset >= chunk.len() {
+            return Ok(0);
+        }
+        let read = buf.len().min(chunk.len() - offset);
+        buf[..read].copy_from_slice(&chunk[offset..offset + read]);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for PyFileReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// Source of workbook opened from Python object, cheap to clone for reopening.
+#[derive(Clone)]
+pub enum WorkbookData {
+    Bytes(Bytes),
+    /// File-like object read on demand
+    File(PyFileReader),
+}
+
+impl WorkbookData {
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        WorkbookData::Bytes(Bytes::Owned(data.into()))
+    }
+
+    /// Read-only buffers are borrowed, writable ones (`bytearray`, etc.) are copied,
+    /// as they could be modified or resized while workbook is open.
+    pub fn from_buffer(py: Python<'_>, buffer: PyBuffer<u8>) -> PyResult<Self> {
+        if !buffer.is_c_contiguous() {
+            return Err(PyValueError::new_err("Buffer must be C-contiguous"));
+        }
+        if !buffer.readonly() {
+            return Ok(WorkbookData::from_vec(buffer.to_vec(py)?));
+        }
+        Ok(WorkbookData::Bytes(Bytes::Buffer(Arc::new(buffer))))
+    }
+
+    /// Maps file at `path` into memory.
+    pub fn from_mmap(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only, the file must not be modified or truncated
+        // while workbook is open (see `CalamineWorkbook::from_path`).
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(WorkbookData::Bytes(Bytes::Mmap(Arc::new(map))))
+    }
+
+    /// `file` must be opened in binary mode and stay open while workbook is used.
+    pub fn from_file(file: PyObject) -> PyResult<Self> {
+        PyFileReader::new(file).map(WorkbookData::File)
+    }
+
+    /// Returns new reader from the start of data.
+    pub fn reader(&self) -> WorkbookReader {
+        match self {
+            WorkbookData::Bytes(bytes) => WorkbookReader::Bytes(Cursor::new(bytes.clone())),
+            WorkbookData::File(file) => {
+                let mut file = file.clone();
+                file.position = 0;
+                WorkbookReader::File(file)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum WorkbookReader {
+    Bytes(Cursor<Bytes>),
+    File(PyFileReader),
+}
+
+impl Read for WorkbookReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            WorkbookReader::Bytes(reader) => reader.read(buf),
+            WorkbookReader::File(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl Seek for WorkbookReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            WorkbookReader::Bytes(reader) => reader.seek(pos),
+            WorkbookReader::File(reader) => reader.seek(pos),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::{Read, Seek};
+
+use calamine::{
+    Data, DataRef, Dimensions, Error as CalamineCrateError, Range, Reader, Xlsb, Xlsx, XlsxError,
+};
+use ouroboros::self_referencing;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use crate::types::sheet::row_to_py;
+use crate::types::usecols::UseCols;
+use crate::utils::err_to_py;
+use crate::{ConvertOptions, Error};
+
+type CellResult = Result<Option<((u32, u32), Data)>, Error>;
+type NextCell<'a> = Box<dyn FnMut() -> CellResult + 'a>;
+
+/// Workbook with streaming reader of worksheet cells.
+trait CellsWorkbook: Send {
+    /// Returns dimensions of sheet and reader of its cells,
+    /// `None` if sheet has no cells (e.g. chartsheet).
+    fn cells_reader<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<Option<(Dimensions, NextCell<'a>)>, Error>;
+}
+
+impl<RS: Read + Seek + Send> CellsWorkbook for Xlsx<RS> {
+    fn cells_reader<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<Option<(Dimensions, NextCell<'a>)>, Error> {
+        match self.worksheet_cells_reader(name) {
+            Ok(mut reader) => Ok(Some((
+                reader.dimensions(),
+                Box::new(move || {
+                    reader
+                        .next_cell()
+                        .map(|cell| cell.map(|c| (c.get_position(), c.get_value().clone())))
+                        .map(to_data_cell)
+                        .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))
+                }),
+            ))),
+            // not a worksheet (e.g. chartsheet), same as empty range in `worksheet_range`
+            Err(XlsxError::NotAWorksheet(_)) => Ok(None),
+            Err(e) => Err(Error::Calamine(CalamineCrateError::Xlsx(e))),
+        }
+    }
+}
+
+impl<RS: Read + Seek + Send> CellsWorkbook for Xlsb<RS> {
+    fn cells_reader<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<Option<(Dimensions, NextCell<'a>)>, Error> {
+        let mut reader = self
+            .worksheet_cells_reader(name)
+            .map_err(|e| Error::Calamine(CalamineCrateError::Xlsb(e)))?;
+        Ok(Some((
+            reader.dimensions(),
+            Box::new(move || {
+                reader
+                    .next_cell()
+                    .map(|cell| cell.map(|c| (c.get_position(), c.get_value().clone())))
+                    .map(to_data_cell)
+                    .map_err(|e| Error::Calamine(CalamineCrateError::Xlsb(e)))
+            }),
+        )))
+    }
+}
+
+fn to_data_cell(cell: Option<((u32, u32), DataRef<'_>)>) -> Option<((u32, u32), Data)> {
+    cell.map(|(position, value)| (position, Data::from(value)))
+}
+
+/// Workbook and the cells reader borrowing it.
+#[self_referencing]
+struct OwnedCells {
+    workbook: Box<dyn CellsWorkbook>,
+    #[borrows(mut workbook)]
+    #[not_covariant]
+    next_cell: Option<NextCell<'this>>,
+}
+
+/// Reads cells of the xlsx/xlsb worksheet one by one, without loading the whole sheet.
+pub struct CellsReader {
+    cells: OwnedCells,
+    dimensions: Dimensions,
+}
+
+// SAFETY: the workbook is `Send` (see `CellsWorkbook`), the reader of cells borrowing it
+// isn't `Send` only because zip entries refer to the archive reader as `&mut dyn Read`.
+// The reader is used by one thread at a time, moved into `allow_threads` for parsing.
+unsafe impl Send for CellsReader {}
+
+impl CellsReader {
+    fn new(workbook: Box<dyn CellsWorkbook>, name: &str) -> Result<Self, Error> {
+        let mut dimensions = Dimensions::default();
+        let cells = OwnedCellsTryBuilder {
+            workbook,
+            next_cell_builder: |workbook: &mut Box<dyn CellsWorkbook>| {
+                workbook.cells_reader(name).map(|reader| {
+                    reader.map(|(sheet_dimensions, next_cell)| {
+                        dimensions = sheet_dimensions;
+                        next_cell
+                    })
+                })
+            },
+        }
+        .try_build()?;
+        Ok(CellsReader { cells, dimensions })
+    }
+
+    pub fn xlsx<RS: Read + Seek + Send + 'static>(reader: RS, name: &str) -> Result<Self, Error> {
+        let xlsx = Xlsx::new(reader).map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))?;
+        CellsReader::new(Box::new(xlsx), name)
+    }
+
+    pub fn xlsb<RS: Read + Seek + Send + 'static>(reader: RS, name: &str) -> Result<Self, Error> {
+        let xlsb = Xlsb::new(reader).map_err(|e| Error::Calamine(CalamineCrateError::Xlsb(e)))?;
+        CellsReader::new(Box::new(xlsb), name)
+    }
+
+    /// Returns next non-empty cell, `None` at the end of sheet.
+    pub fn next_cell(&mut self) -> CellResult {
+        self.cells.with_next_cell_mut(|cells| {
+            let Some(next_cell) = cells.as_mut() else {
+                return Ok(None);
+            };
+            loop {
+                match next_cell()? {
+                    Some((_, Data::Empty)) => continue,
+                    Some(cell) => return Ok(Some(cell)),
+                    None => {
+                        // calamine readers fail if called after the end
+                        *cells = None;
+                        return Ok(None);
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+}
+
+pub enum RowSource {
+    /// Streaming reader for xlsx and xlsb
+    Cells(CellsReader),
+    /// Whole sheet, for formats without streaming reader (xls, ods)
+    Range(Range<Data>),
+}
+
+#[pyclass]
+pub struct CalamineRowStream {
+    source: RowSource,
+    options: ConvertOptions,
+    row: u32,
+    width: usize,
+    pending: Option<((u32, u32), Data)>,
+    /// Columns to select, resolved by the first row
+    usecols: Option<UseCols>,
+    /// Positions of selected columns, all columns if `None`
+    columns: Option<Vec<usize>>,
+}
+
+impl CalamineRowStream {
+    pub fn new(source: RowSource, options: ConvertOptions, usecols: Option<UseCols>) -> Self {
+        let width = match &source {
+            RowSource::Cells(reader) => reader.dimensions().end.1 as usize + 1,
+            RowSource::Range(range) => range.end().map_or(0, |end| end.1 as usize + 1),
+        };
+        CalamineRowStream {
+            source,
+            options,
+            row: 0,
+            width,
+            pending: None,
+            usecols,
+            columns: None,
+        }
+    }
+
+    /// Returns the row with index `self.row`, starting from the first column.
+    fn next_row(&mut self) -> Result<Option<Vec<Data>>, Error> {
+        match &mut self.source {
+            RowSource::Range(range) => match range.end() {
+                Some((end_row, _)) if self.row <= end_row => Ok(Some(
+                    (0..self.width as u32)
+                        .map(|col| {
+                            range
+                                .get_value((self.row, col))
+                                .cloned()
+                                .unwrap_or(Data::Empty)
+                        })
+                        .collect(),
+                )),
+                _ => Ok(None),
+            },
+            RowSource::Cells(reader) => {
+                if self.pending.is_none() {
+                    self.pending = reader.next_cell()?;
+                    if self.pending.is_none() {
+                        return Ok(None);
+                    }
+                }
+                let mut row = vec![Data::Empty; self.width];
+                while let Some(((row_index, col), value)) = self.pending.take() {
+                    if row_index > self.row {
+                        self.pending = Some(((row_index, col), value));
+                        break;
+                    }
+                    // cells outside of the stored dimension widen this and the next rows
+                    let col = col as usize;
+                    if col >= row.len() {
+                        row.resize(col + 1, Data::Empty);
+                        self.width = row.len();
+                    }
+                    row[col] = value;
+                    self.pending = reader.next_cell()?;
+                }
+                Ok(Some(row))
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl CalamineRowStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Bound<'_, PyList>>> {
+        let py = slf.py();
+        let slf = &mut *slf;
+        let row = py.allow_threads(|| slf.next_row()).map_err(err_to_py)?;
+        if let (Some(usecols), Some(row)) = (slf.usecols.take(), &row) {
+            // rows start from the cell A1, names are taken from the first one
+            slf.columns = Some(usecols.row_positions(row)?);
+        }
+        let position = (slf.row, 0);
+        slf.row += 1;
+        row.map(|row| row_to_py(py, &row, position, slf.columns.as_deref(), &slf.options))
+            .transpose()
+    }
+}
@@ -0,0 +1,48 @@
+use calamine::{Data, Table};
+use pyo3::prelude::*;
+
+use crate::{CalamineSheet, DatesMode};
+
+#[pyclass]
+pub struct CalamineTable {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    sheet_name: String,
+    #[pyo3(get)]
+    columns: Vec<String>,
+    #[pyo3(get)]
+    data: Py<CalamineSheet>,
+}
+
+impl CalamineTable {
+    pub fn new(
+        py: Python<'_>,
+        table: Table<Data>,
+        empty_value: Option<PyObject>,
+        dates: DatesMode,
+    ) -> PyResult<Self> {
+        let name = table.name().to_owned();
+        let sheet_name = table.sheet_name().to_owned();
+        let columns = table.columns().to_vec();
+        let sheet = CalamineSheet::new(sheet_name.clone(), table.into())
+            .with_empty_value(empty_value)
+            .with_dates(dates);
+        Ok(CalamineTable {
+            name,
+            sheet_name,
+            columns,
+            data: Py::new(py, sheet)?,
+        })
+    }
+}
+
+#[pymethods]
+impl CalamineTable {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "CalamineTable(name='{}', sheet_name='{}')",
+            self.name, self.sheet_name
+        ))
+    }
+}
@@ -0,0 +1,158 @@
+use calamine::{Data, Range};
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::utils::{column_index, header_names};
+
+/// Columns selected by `usecols`.
+pub enum UseCols {
+    /// Zero-based positions of columns in the data (after skipping empty area)
+    Positions(Vec<usize>),
+    /// Absolute (first, last) columns from Excel letters like `A:C,F`
+    Letters(Vec<(u32, u32)>),
+    /// Column names from the header row
+    Names(Vec<String>),
+}
+
+/// Index of the last column of Excel sheet (`XFD`)
+const MAX_COLUMN: u32 = 16_383;
+
+fn parse_letters(spec: &str) -> PyResult<Vec<(u32, u32)>> {
+    spec.split(',')
+        .map(|part| {
+            let part = part.trim();
+            let (first, last) = part.split_once(':').unwrap_or((part, part));
+            let column = |letters: &str| column_index(letters.trim()).filter(|&c| c <= MAX_COLUMN);
+            match (column(first), column(last)) {
+                (Some(first), Some(last)) => Ok((first.min(last), first.max(last))),
+                _ => Err(PyValueError::new_err(format!(
+                    "usecols str must contain column letters up to XFD like 'A:C,F' \
+                     (names are given as list), got '{}'",
+                    spec
+                ))),
+            }
+        })
+        .collect()
+}
+
+impl<'py> FromPyObject<'py> for UseCols {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(spec) = ob.extract::<String>() {
+            return parse_letters(&spec).map(UseCols::Letters);
+        }
+        if let Ok(positions) = ob.extract::<Vec<usize>>() {
+            return Ok(UseCols::Positions(positions));
+        }
+        if let Ok(names) = ob.extract::<Vec<String>>() {
+            return Ok(UseCols::Names(names));
+        }
+        Err(PyValueError::new_err(
+            "usecols must be str with column letters, list of int or list of str",
+        ))
+    }
+}
+
+impl UseCols {
+    /// Returns positions of selected columns in `range`. Letters outside of range are skipped,
+    /// names are looked up in `header_rows` (relative to range start, see `header_names`).
+    pub fn positions(&self, range: &Range<Data>, header_rows: &[usize]) -> PyResult<Vec<usize>> {
+        let width = range.width();
+        match self {
+            UseCols::Positions(positions) => {
+                if let Some(position) = positions.iter().find(|&&position| position >= width) {
+                    return Err(PyIndexError::new_err(format!(
+                        "usecols position {} is out of data width {}",
+                        position, width
+                    )));
+                }
+                Ok(positions.clone())
+            }
+            UseCols::Letters(letters) => {
+                let start = range.start().map_or(0, |start| start.1);
+                Ok(letters
+                    .iter()
+                    .flat_map(|&(first, last)| first..=last)
+                    .filter(|&col| col >= start && ((col - start) as usize) < width)
+                    .map(|col| (col - start) as usize)
+                    .collect())
+            }
+            UseCols::Names(names) => {
+                let header: Vec<&[Data]> = header_rows
+                    .iter()
+                    .filter_map(|&row| range.rows().nth(row))
+                    .collect();
+                let columns = header_names(&header, width);
+                names
+                    .iter()
+                    .map(|name| {
+                        columns
+                            .iter()
+                            .position(|column| column == name)
+                            .ok_or_else(|| {
+                                PyValueError::new_err(format!(
+                                    "usecols name '{}' is not found in header",
+                                    name
+                                ))
+                            })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns positions of selected columns in rows starting from column A,
+    /// names are looked up in `header`.
+    pub fn row_positions(&self, header: &[Data]) -> PyResult<Vec<usize>> {
+        let Some(last) = header.len().checked_sub(1) else {
+            return self.positions(&Range::empty(), &[]);
+        };
+        let mut range = Range::new((0, 0), (0, last as u32));
+        for (col, value) in header.iter().enumerate() {
+            range.set_value((0, col as u32), value.clone());
+        }
+        self.positions(&range, &[0])
+    }
+
+    /// Returns positions of selected columns in `range`, which is `data` padded
+    /// to the cell A1 if empty area isn't skipped. Names are looked up in the first
+    /// row of `data`, not in the padded top of sheet.
+    pub fn data_positions(&self, range: &Range<Data>, data: &Range<Data>) -> PyResult<Vec<usize>> {
+        let UseCols::Names(_) = self else {
+            return self.positions(range, &[0]);
+        };
+        let offset = match (data.start(), range.start()) {
+            (Some(data_start), Some(start)) => (data_start.1 - start.1) as usize,
+            _ => 0,
+        };
+        Ok(self
+            .positions(data, &[0])?
+            .into_iter()
+            .map(|position| position + offset)
+            .collect())
+    }
+}
+
+/// Returns positions of `usecols` in `range` or all columns, if omitted.
+pub fn selected_columns(
+    usecols: Option<&UseCols>,
+    range: &Range<Data>,
+    header_rows: &[usize],
+) -> PyResult<Vec<usize>> {
+    match usecols {
+        Some(usecols) => usecols.positions(range, header_rows),
+        None => Ok((0..range.width()).collect()),
+    }
+}
+
+/// Returns positions of `usecols` in `range` (padded `data`, see `UseCols::data_positions`)
+/// or all columns, if omitted.
+pub fn selected_data_columns(
+    usecols: Option<&UseCols>,
+    range: &Range<Data>,
+    data: &Range<Data>,
+) -> PyResult<Vec<usize>> {
+    match usecols {
+        Some(usecols) => usecols.data_positions(range, data),
+        None => Ok((0..range.width()).collect()),
+    }
+}
@@ -1,19 +1,131 @@
 use std::fs::File;
-use std::io::{BufReader, Cursor, Read};
-use std::path::PathBuf;
+use std::io::{BufReader, Read, Seek};
+use std::path::{Path, PathBuf};
 
-use calamine::{open_workbook_auto, open_workbook_auto_from_rs, Reader, Sheets};
-use pyo3::exceptions::PyTypeError;
+use calamine::{
+    Cell, Data, Dimensions, Error as CalamineCrateError, Ods, Range, Reader, Sheets, Table, Xls,
+    Xlsb, Xlsx, XlsxError,
+};
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyString, PyType};
 use pyo3_file::PyFileLikeObject;
 
-use crate::utils::err_to_py;
-use crate::{CalamineSheet, Error, SheetMetadata, WorksheetNotFound};
+use crate::types::source::{WorkbookData, WorkbookReader};
+use crate::types::stream::{CalamineRowStream, CellsReader, RowSource};
+use crate::types::usecols::UseCols;
+use crate::utils::{err_to_py, parse_sheet_reference};
+use crate::xlsx::{defined_names_scopes, worksheet_number_formats, NumberFormats};
+use crate::{ods, xlsb};
+use crate::{
+    CalamineError, CalamineSheet, CalamineTable, ConvertOptions, DatesMode, EmptyValueArg, Error,
+    ErrorsMode, SheetMetadata, WorksheetNotFound,
+};
+
+/// (name, formula, scope)
+type DefinedName = (String, String, Option<String>);
+
+/// Format of workbook given explicitly instead of detecting it by extension or content,
+/// also reported by `detect_format`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorkbookFormat {
+    Xlsx,
+    /// Macro-enabled xlsx, read the same way
+    Xlsm,
+    Xlsb,
+    Xls,
+    Ods,
+}
+
+impl WorkbookFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkbookFormat::Xlsx => "xlsx",
+            WorkbookFormat::Xlsm => "xlsm",
+            WorkbookFormat::Xlsb => "xlsb",
+            WorkbookFormat::Xls => "xls",
+            WorkbookFormat::Ods => "ods",
+        }
+    }
+
+    /// Format by extension of `path`, like `open_workbook_auto`.
+    fn from_extension(path: &str) -> Option<Self> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("xls") | Some("xla") => Some(WorkbookFormat::Xls),
+            Some("xlsx") | Some("xlsm") | Some("xlam") => Some(WorkbookFormat::Xlsx),
+            Some("xlsb") => Some(WorkbookFormat::Xlsb),
+            Some("ods") => Some(WorkbookFormat::Ods),
+            _ => None,
+        }
+    }
+
+    pub fn open<RS: Read + Seek>(self, reader: RS) -> Result<Sheets<RS>, CalamineCrateError> {
+        match self {
+            WorkbookFormat::Xlsx | WorkbookFormat::Xlsm => Xlsx::new(reader)
+                .map(Sheets::Xlsx)
+                .map_err(CalamineCrateError::Xlsx),
+            WorkbookFormat::Xlsb => Xlsb::new(reader)
+                .map(Sheets::Xlsb)
+                .map_err(CalamineCrateError::Xlsb),
+            WorkbookFormat::Xls => Xls::new(reader)
+                .map(Sheets::Xls)
+                .map_err(CalamineCrateError::Xls),
+            WorkbookFormat::Ods => Ods::new(reader)
+                .map(Sheets::Ods)
+                .map_err(CalamineCrateError::Ods),
+        }
+    }
+
+    /// Opens workbook of `format`, without it tries every format in turn like
+    /// `open_workbook_auto`. `reader` returns new reader from the start of data.
+    fn open_or_detect<RS: Read + Seek>(
+        format: Option<Self>,
+        mut reader: impl FnMut() -> Result<RS, CalamineCrateError>,
+    ) -> Result<Sheets<RS>, CalamineCrateError> {
+        if let Some(format) = format {
+            return format.open(reader()?);
+        }
+        for format in [
+            WorkbookFormat::Xls,
+            WorkbookFormat::Xlsx,
+            WorkbookFormat::Xlsb,
+            WorkbookFormat::Ods,
+        ] {
+            if let Ok(sheets) = format.open(reader()?) {
+                return Ok(sheets);
+            }
+        }
+        Err(CalamineCrateError::Msg("Cannot detect file format"))
+    }
+}
+
+impl<'py> FromPyObject<'py> for WorkbookFormat {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "xlsx" => Ok(WorkbookFormat::Xlsx),
+            "xlsm" => Ok(WorkbookFormat::Xlsm),
+            "xlsb" => Ok(WorkbookFormat::Xlsb),
+            "xls" => Ok(WorkbookFormat::Xls),
+            "ods" => Ok(WorkbookFormat::Ods),
+            other => Err(PyValueError::new_err(format!(
+                "format must be one of 'xlsx', 'xlsm', 'xlsb', 'xls' or 'ods', got '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for WorkbookFormat {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.as_str().into_py(py)
+    }
+}
 
 enum SheetsEnum {
     File(Sheets<BufReader<File>>),
-    FileLike(Sheets<Cursor<Vec<u8>>>),
+    /// Sheets and its data, kept for reopening
+    FileLike(Sheets<WorkbookReader>, WorkbookData),
     None,
 }
 
@@ -21,7 +133,7 @@ impl SheetsEnum {
     fn sheets_metadata(&self) -> Vec<SheetMetadata> {
         match self {
             SheetsEnum::File(f) => f.sheets_metadata(),
-            SheetsEnum::FileLike(f) => f.sheets_metadata(),
+            SheetsEnum::FileLike(f, _) => f.sheets_metadata(),
             SheetsEnum::None => unreachable!(),
         }
         .iter()
@@ -29,10 +141,49 @@ impl SheetsEnum {
         .collect()
     }
 
+    /// Defined names as (name, formula), as read by calamine.
+    fn names(&self) -> Result<&[(String, String)], Error> {
+        match self {
+            SheetsEnum::File(f) => Ok(f.defined_names()),
+            SheetsEnum::FileLike(f, _) => Ok(f.defined_names()),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    /// Defined names as (name, formula, scope), `None` if scopes are unknown
+    /// (formats other than xlsx). `path` is used to reopen the file for reading scopes.
+    fn defined_names(&self, path: Option<&str>) -> Result<Option<Vec<DefinedName>>, Error> {
+        let names = self.names()?;
+        let scopes = match self {
+            _ if names.is_empty() => return Ok(Some(Vec::new())),
+            SheetsEnum::File(Sheets::Xlsx(_)) => defined_names_scopes(reopen_file(path)?),
+            SheetsEnum::FileLike(Sheets::Xlsx(_), data) => defined_names_scopes(data.reader()),
+            _ => return Ok(None),
+        }
+        .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))?;
+
+        // same names in the same order, otherwise scopes are unknown
+        if scopes.len() != names.len()
+            || scopes
+                .iter()
+                .zip(names)
+                .any(|(scope, name)| scope.0 != name.0)
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            names
+                .iter()
+                .zip(scopes)
+                .map(|((name, formula), (_, scope))| (name.clone(), formula.clone(), scope))
+                .collect(),
+        ))
+    }
+
     fn sheet_names(&self) -> Vec<String> {
         match self {
             SheetsEnum::File(f) => f.sheet_names(),
-            SheetsEnum::FileLike(f) => f.sheet_names(),
+            SheetsEnum::FileLike(f, _) => f.sheet_names(),
             SheetsEnum::None => unreachable!(),
         }
     }
@@ -40,10 +191,230 @@ impl SheetsEnum {
     fn worksheet_range(&mut self, name: &str) -> Result<calamine::Range<calamine::Data>, Error> {
         match self {
             SheetsEnum::File(f) => f.worksheet_range(name).map_err(Error::Calamine),
-            SheetsEnum::FileLike(f) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::FileLike(f, _) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn worksheet_range_rows(
+        &mut self,
+        name: &str,
+        skiprows: u32,
+        nrows: Option<u32>,
+    ) -> Result<Range<Data>, Error> {
+        match self {
+            SheetsEnum::File(f) => range_rows(f, name, skiprows, nrows),
+            SheetsEnum::FileLike(f, _) => range_rows(f, name, skiprows, nrows),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn worksheet_formula(&mut self, name: &str) -> Result<calamine::Range<String>, Error> {
+        match self {
+            SheetsEnum::File(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::FileLike(f, _) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    /// Names of tables in workbook or in sheet `sheet_name`.
+    fn table_names(&mut self, sheet_name: Option<&str>) -> Result<Vec<String>, Error> {
+        match self {
+            SheetsEnum::File(f) => table_names(f, sheet_name),
+            SheetsEnum::FileLike(f, _) => table_names(f, sheet_name),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
     }
+
+    fn table_by_name(&mut self, name: &str) -> Result<Table<Data>, Error> {
+        match self {
+            SheetsEnum::File(f) => tables(f)?.table_by_name(name),
+            SheetsEnum::FileLike(f, _) => tables(f)?.table_by_name(name),
+            SheetsEnum::None => return Err(Error::WorkbookClosed),
+        }
+        .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))
+    }
+
+    /// Returns `None` if merged regions of sheet are unknown.
+    /// `path` is used to reopen xlsb and ods, calamine doesn't read their merged cells.
+    fn worksheet_merge_cells(
+        &mut self,
+        name: &str,
+        path: Option<&str>,
+    ) -> Result<Option<Vec<Dimensions>>, Error> {
+        let merged = match self {
+            SheetsEnum::File(Sheets::Xlsb(_)) => {
+                xlsb::worksheet_merge_cells(reopen_file(path)?, name)
+                    .map_err(CalamineCrateError::Xlsb)
+            }
+            SheetsEnum::File(Sheets::Ods(_)) => {
+                ods::worksheet_merge_cells(reopen_file(path)?, name)
+                    .map_err(CalamineCrateError::Ods)
+            }
+            SheetsEnum::FileLike(Sheets::Xlsb(_), data) => {
+                xlsb::worksheet_merge_cells(data.reader(), name).map_err(CalamineCrateError::Xlsb)
+            }
+            SheetsEnum::FileLike(Sheets::Ods(_), data) => {
+                ods::worksheet_merge_cells(data.reader(), name).map_err(CalamineCrateError::Ods)
+            }
+            SheetsEnum::File(f) => return merge_cells(f, name),
+            SheetsEnum::FileLike(f, _) => return merge_cells(f, name),
+            SheetsEnum::None => return Err(Error::WorkbookClosed),
+        };
+        merged.map(Some).map_err(Error::Calamine)
+    }
+
+    /// Number format codes of cells, supported for xlsx only.
+    /// `path` is used to reopen the file, calamine doesn't read styles of cells.
+    fn number_formats(&self, name: &str, path: Option<&str>) -> Result<NumberFormats, Error> {
+        let formats = match self {
+            SheetsEnum::File(Sheets::Xlsx(_)) => worksheet_number_formats(reopen_file(path)?, name),
+            SheetsEnum::FileLike(Sheets::Xlsx(_), data) => {
+                worksheet_number_formats(data.reader(), name)
+            }
+            SheetsEnum::None => return Err(Error::WorkbookClosed),
+            _ => {
+                return Err(Error::Calamine(CalamineCrateError::Msg(
+                    "Number formats are supported only for xlsx",
+                )))
+            }
+        };
+        formats.map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))
+    }
+
+    /// Streaming rows source for xlsx and xlsb, whole sheet for other formats.
+    /// `path` is used to reopen the file, streaming reader borrows its own workbook reader.
+    fn row_source(&mut self, name: &str, path: Option<&str>) -> Result<RowSource, Error> {
+        match self {
+            SheetsEnum::File(Sheets::Xlsx(_)) => {
+                CellsReader::xlsx(reopen_file(path)?, name).map(RowSource::Cells)
+            }
+            SheetsEnum::File(Sheets::Xlsb(_)) => {
+                CellsReader::xlsb(reopen_file(path)?, name).map(RowSource::Cells)
+            }
+            SheetsEnum::FileLike(Sheets::Xlsx(_), data) => {
+                CellsReader::xlsx(data.reader(), name).map(RowSource::Cells)
+            }
+            SheetsEnum::FileLike(Sheets::Xlsb(_), data) => {
+                CellsReader::xlsb(data.reader(), name).map(RowSource::Cells)
+            }
+            _ => self.worksheet_range(name).map(RowSource::Range),
+        }
+    }
+}
+
+/// Reads only rows `skiprows..skiprows + nrows`, xlsx and xlsb stop parsing after them.
+fn range_rows<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    name: &str,
+    skiprows: u32,
+    nrows: Option<u32>,
+) -> Result<Range<Data>, Error> {
+    let end = nrows.map(|nrows| skiprows.saturating_add(nrows));
+    let mut cells = Vec::new();
+    // returns `false` when all rows are read
+    let mut push = |position: (u32, u32), value: Data| {
+        if end.is_some_and(|end| position.0 >= end) {
+            return false;
+        }
+        if position.0 >= skiprows && value != Data::Empty {
+            cells.push(Cell::new(position, value));
+        }
+        true
+    };
+
+    match sheets {
+        Sheets::Xlsx(xlsx) => {
+            let mut reader = match xlsx.worksheet_cells_reader(name) {
+                Ok(reader) => reader,
+                Err(XlsxError::NotAWorksheet(_)) => return Ok(Range::default()),
+                Err(e) => return Err(Error::Calamine(CalamineCrateError::Xlsx(e))),
+            };
+            while let Some(cell) = reader
+                .next_cell()
+                .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))?
+            {
+                if !push(cell.get_position(), cell.get_value().clone().into()) {
+                    break;
+                }
+            }
+        }
+        Sheets::Xlsb(xlsb) => {
+            let mut reader = xlsb
+                .worksheet_cells_reader(name)
+                .map_err(|e| Error::Calamine(CalamineCrateError::Xlsb(e)))?;
+            while let Some(cell) = reader
+                .next_cell()
+                .map_err(|e| Error::Calamine(CalamineCrateError::Xlsb(e)))?
+            {
+                if !push(cell.get_position(), cell.get_value().clone().into()) {
+                    break;
+                }
+            }
+        }
+        Sheets::Xls(_) | Sheets::Ods(_) => {
+            let range = sheets.worksheet_range(name).map_err(Error::Calamine)?;
+            let start = range.start().unwrap_or_default();
+            for (row, col, value) in range.used_cells() {
+                let position = (start.0 + row as u32, start.1 + col as u32);
+                if !push(position, value.clone()) {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(Range::from_sparse(cells))
+}
+
+/// Returns xlsx reader with loaded tables, other formats don't support tables.
+fn tables<RS: Read + Seek>(sheets: &mut Sheets<RS>) -> Result<&mut Xlsx<RS>, Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx) => {
+            xlsx.load_tables()
+                .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e)))?;
+            Ok(xlsx)
+        }
+        _ => Err(Error::Calamine(CalamineCrateError::Msg(
+            "Tables are supported only for xlsx",
+        ))),
+    }
+}
+
+fn table_names<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    sheet_name: Option<&str>,
+) -> Result<Vec<String>, Error> {
+    let xlsx = tables(sheets)?;
+    let names = match sheet_name {
+        Some(sheet_name) => xlsx.table_names_in_sheet(sheet_name),
+        None => xlsx.table_names(),
+    };
+    Ok(names.into_iter().cloned().collect())
+}
+
+/// Reopens file of workbook opened from path.
+fn reopen_file(path: Option<&str>) -> Result<BufReader<File>, Error> {
+    let path = path.ok_or(Error::Calamine(CalamineCrateError::Msg(
+        "Workbook opened from file has no path",
+    )))?;
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| Error::Calamine(CalamineCrateError::Io(e)))
+}
+
+fn merge_cells<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    name: &str,
+) -> Result<Option<Vec<Dimensions>>, Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx) => xlsx
+            .worksheet_merge_cells(name)
+            .transpose()
+            .map_err(|e| Error::Calamine(CalamineCrateError::Xlsx(e))),
+        Sheets::Xls(xls) => Ok(xls.worksheet_merge_cells(name)),
+        // read by `SheetsEnum::worksheet_merge_cells`
+        Sheets::Xlsb(_) | Sheets::Ods(_) => Ok(None),
+    }
 }
 
 #[pyclass]
@@ -55,6 +426,10 @@ pub struct CalamineWorkbook {
     sheets_metadata: Vec<SheetMetadata>,
     #[pyo3(get)]
     sheet_names: Vec<String>,
+    /// Read on first use, `None` until then
+    defined_names: Option<Vec<DefinedName>>,
+    empty_value: Option<PyObject>,
+    dates: DatesMode,
 }
 
 #[pymethods]
@@ -67,49 +442,233 @@ impl CalamineWorkbook {
     }
 
     #[classmethod]
-    #[pyo3(name = "from_object")]
+    #[pyo3(
+        name = "from_object",
+        signature = (path_or_filelike, mmap=false, format=None)
+    )]
     fn py_from_object(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         path_or_filelike: PyObject,
+        mmap: bool,
+        format: Option<WorkbookFormat>,
     ) -> PyResult<Self> {
-        Self::from_object(py, path_or_filelike)
+        Self::from_object(py, path_or_filelike, mmap, format)
     }
 
     #[classmethod]
-    #[pyo3(name = "from_filelike")]
+    #[pyo3(name = "from_filelike", signature = (filelike, lazy=false, format=None))]
     fn py_from_filelike(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         filelike: PyObject,
+        lazy: bool,
+        format: Option<WorkbookFormat>,
+    ) -> PyResult<Self> {
+        py.allow_threads(|| Self::from_filelike(filelike, lazy, format))
+    }
+
+    #[classmethod]
+    #[pyo3(name = "from_bytes", signature = (data, format=None))]
+    fn py_from_bytes(
+        _cls: &Bound<'_, PyType>,
+        py: Python<'_>,
+        data: &Bound<'_, PyAny>,
+        format: Option<WorkbookFormat>,
     ) -> PyResult<Self> {
-        py.allow_threads(|| Self::from_filelike(filelike))
+        let data = WorkbookData::from_buffer(py, PyBuffer::get_bound(data)?)?;
+        py.allow_threads(|| Self::from_data(data, format))
     }
 
     #[classmethod]
-    #[pyo3(name = "from_path")]
-    fn py_from_path(_cls: &Bound<'_, PyType>, py: Python<'_>, path: PyObject) -> PyResult<Self> {
+    #[pyo3(name = "from_path", signature = (path, mmap=false, format=None))]
+    fn py_from_path(
+        _cls: &Bound<'_, PyType>,
+        py: Python<'_>,
+        path: PyObject,
+        mmap: bool,
+        format: Option<WorkbookFormat>,
+    ) -> PyResult<Self> {
         if let Ok(string_ref) = path.downcast_bound::<PyString>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.allow_threads(|| Self::from_path(&path));
+            return py.allow_threads(|| Self::from_path(&path, mmap, format));
         }
 
         if let Ok(string_ref) = path.extract::<PathBuf>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.allow_threads(|| Self::from_path(&path));
+            return py.allow_threads(|| Self::from_path(&path, mmap, format));
         }
 
         Err(PyTypeError::new_err(""))
     }
 
-    #[pyo3(name = "get_sheet_by_name")]
-    fn py_get_sheet_by_name(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineSheet> {
-        py.allow_threads(|| self.get_sheet_by_name(name))
+    #[getter]
+    fn get_empty_value(&self, py: Python<'_>) -> PyObject {
+        match &self.empty_value {
+            Some(value) => value.clone_ref(py),
+            None => "".to_object(py),
+        }
     }
 
-    #[pyo3(name = "get_sheet_by_index")]
-    fn py_get_sheet_by_index(&mut self, py: Python<'_>, index: usize) -> PyResult<CalamineSheet> {
-        py.allow_threads(|| self.get_sheet_by_index(index))
+    #[setter]
+    fn set_empty_value(&mut self, value: PyObject) {
+        self.empty_value = Some(value);
+    }
+
+    #[getter]
+    fn get_dates(&self, py: Python<'_>) -> PyObject {
+        self.dates.to_object(py)
+    }
+
+    #[setter]
+    fn set_dates(&mut self, dates: DatesMode) {
+        self.dates = dates;
+    }
+
+    #[pyo3(
+        name = "get_sheet_by_name",
+        signature = (
+            name,
+            formulas=false,
+            merged_cells=false,
+            nrows=None,
+            skiprows=0,
+            number_formats=false,
+        )
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn py_get_sheet_by_name(
+        &mut self,
+        py: Python<'_>,
+        name: &str,
+        formulas: bool,
+        merged_cells: bool,
+        nrows: Option<u32>,
+        skiprows: u32,
+        number_formats: bool,
+    ) -> PyResult<CalamineSheet> {
+        let empty_value = self.empty_value.as_ref().map(|v| v.clone_ref(py));
+        py.allow_threads(|| {
+            self.get_sheet_by_name(
+                name,
+                formulas,
+                merged_cells,
+                nrows,
+                skiprows,
+                number_formats,
+            )
+        })
+        .map(|sheet| sheet.with_empty_value(empty_value).with_dates(self.dates))
+    }
+
+    #[pyo3(
+        name = "get_sheet_by_index",
+        signature = (
+            index,
+            formulas=false,
+            merged_cells=false,
+            nrows=None,
+            skiprows=0,
+            number_formats=false,
+        )
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn py_get_sheet_by_index(
+        &mut self,
+        py: Python<'_>,
+        index: usize,
+        formulas: bool,
+        merged_cells: bool,
+        nrows: Option<u32>,
+        skiprows: u32,
+        number_formats: bool,
+    ) -> PyResult<CalamineSheet> {
+        let empty_value = self.empty_value.as_ref().map(|v| v.clone_ref(py));
+        py.allow_threads(|| {
+            self.get_sheet_by_index(
+                index,
+                formulas,
+                merged_cells,
+                nrows,
+                skiprows,
+                number_formats,
+            )
+        })
+        .map(|sheet| sheet.with_empty_value(empty_value).with_dates(self.dates))
+    }
+
+    #[pyo3(
+        name = "iter_sheet_rows",
+        signature = (
+            name,
+            errors=ErrorsMode::Value,
+            empty_value=EmptyValueArg::Default,
+            dates=None,
+            usecols=None,
+        )
+    )]
+    fn py_iter_sheet_rows(
+        &mut self,
+        py: Python<'_>,
+        name: &str,
+        errors: ErrorsMode,
+        empty_value: EmptyValueArg,
+        dates: Option<DatesMode>,
+        usecols: Option<UseCols>,
+    ) -> PyResult<CalamineRowStream> {
+        let options = ConvertOptions::new(py, errors, empty_value, self.empty_value.as_ref())
+            .with_dates(dates.unwrap_or(self.dates));
+        let source = py
+            .allow_threads(|| self.sheets.row_source(name, self.path.as_deref()))
+            .map_err(err_to_py)?;
+        Ok(CalamineRowStream::new(source, options, usecols))
+    }
+
+    #[pyo3(name = "get_named_range")]
+    fn py_get_named_range(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineSheet> {
+        let empty_value = self.empty_value.as_ref().map(|v| v.clone_ref(py));
+        py.allow_threads(|| self.get_named_range(name))
+            .map(|sheet| sheet.with_empty_value(empty_value).with_dates(self.dates))
+    }
+
+    #[getter]
+    fn defined_names(&mut self) -> PyResult<Vec<DefinedName>> {
+        if let Some(names) = self.load_defined_names().map_err(err_to_py)? {
+            return Ok(names.clone());
+        }
+        // scopes are unknown, names are returned as workbook-level ones
+        Ok(self
+            .sheets
+            .names()
+            .map_err(err_to_py)?
+            .iter()
+            .map(|(name, formula)| (name.clone(), formula.clone(), None))
+            .collect())
+    }
+
+    #[getter]
+    fn table_names(&mut self, py: Python<'_>) -> PyResult<Vec<String>> {
+        py.allow_threads(|| self.sheets.table_names(None))
+            .map_err(err_to_py)
+    }
+
+    #[pyo3(name = "table_names_in_sheet")]
+    fn py_table_names_in_sheet(
+        &mut self,
+        py: Python<'_>,
+        sheet_name: &str,
+    ) -> PyResult<Vec<String>> {
+        py.allow_threads(|| self.sheets.table_names(Some(sheet_name)))
+            .map_err(err_to_py)
+    }
+
+    #[pyo3(name = "get_table")]
+    fn py_get_table(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineTable> {
+        let empty_value = self.empty_value.as_ref().map(|v| v.clone_ref(py));
+        let table = py
+            .allow_threads(|| self.sheets.table_by_name(name))
+            .map_err(err_to_py)?;
+        CalamineTable::new(py, table, empty_value, self.dates)
     }
 
     fn close(&mut self) -> PyResult<()> {
@@ -138,30 +697,51 @@ impl CalamineWorkbook {
 }
 
 impl CalamineWorkbook {
-    pub fn from_object(py: Python<'_>, path_or_filelike: PyObject) -> PyResult<Self> {
+    /// `mmap` is used for paths only.
+    /// If `format` is `None`, it's detected by extension of path or content of data.
+    pub fn from_object(
+        py: Python<'_>,
+        path_or_filelike: PyObject,
+        mmap: bool,
+        format: Option<WorkbookFormat>,
+    ) -> PyResult<Self> {
         if let Ok(string_ref) = path_or_filelike.downcast_bound::<PyString>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.allow_threads(|| Self::from_path(&path));
+            return py.allow_threads(|| Self::from_path(&path, mmap, format));
         }
 
         if let Ok(string_ref) = path_or_filelike.extract::<PathBuf>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.allow_threads(|| Self::from_path(&path));
+            return py.allow_threads(|| Self::from_path(&path, mmap, format));
         }
 
-        py.allow_threads(|| Self::from_filelike(path_or_filelike))
+        if let Ok(buffer) = PyBuffer::<u8>::get_bound(path_or_filelike.bind(py)) {
+            let data = WorkbookData::from_buffer(py, buffer)?;
+            return py.allow_threads(|| Self::from_data(data, format));
+        }
+
+        py.allow_threads(|| Self::from_filelike(path_or_filelike, false, format))
     }
 
-    pub fn from_filelike(filelike: PyObject) -> PyResult<Self> {
+    /// If `lazy`, the file is read on demand, otherwise it's read into memory at once.
+    pub fn from_filelike(
+        filelike: PyObject,
+        lazy: bool,
+        format: Option<WorkbookFormat>,
+    ) -> PyResult<Self> {
+        if lazy {
+            return Self::from_data(WorkbookData::from_file(filelike)?, format);
+        }
         let mut buf = vec![];
         PyFileLikeObject::with_requirements(filelike, true, false, true, false)?
             .read_to_end(&mut buf)?;
-        let reader = Cursor::new(buf);
-        let sheets = SheetsEnum::FileLike(
-            open_workbook_auto_from_rs(reader)
-                .map_err(Error::Calamine)
-                .map_err(err_to_py)?,
-        );
+        Self::from_data(WorkbookData::from_vec(buf), format)
+    }
+
+    fn from_data(data: WorkbookData, format: Option<WorkbookFormat>) -> PyResult<Self> {
+        let sheets = WorkbookFormat::open_or_detect(format, || Ok(data.reader()));
+        let sheets =
+            SheetsEnum::FileLike(sheets.map_err(Error::Calamine).map_err(err_to_py)?, data);
         let sheet_names = sheets.sheet_names().to_owned();
         let sheets_metadata = sheets.sheets_metadata().to_owned();
 
@@ -170,15 +750,31 @@ impl CalamineWorkbook {
             sheets,
             sheets_metadata,
             sheet_names,
+            defined_names: None,
+            empty_value: None,
+            dates: DatesMode::Infer,
         })
     }
 
-    pub fn from_path(path: &str) -> PyResult<Self> {
-        let sheets = SheetsEnum::File(
-            open_workbook_auto(path)
-                .map_err(Error::Calamine)
-                .map_err(err_to_py)?,
-        );
+    /// If `mmap`, the file is read through read-only memory map. The file must not be
+    /// modified while workbook is open, reading pages of truncated file raises SIGBUS.
+    /// If `format` is `None`, it's detected by extension, by content for unknown extensions.
+    pub fn from_path(path: &str, mmap: bool, format: Option<WorkbookFormat>) -> PyResult<Self> {
+        let format = format.or_else(|| WorkbookFormat::from_extension(path));
+        if mmap {
+            let data = WorkbookData::from_mmap(path)
+                .map_err(|e| err_to_py(Error::Calamine(CalamineCrateError::Io(e))))?;
+            let mut workbook = Self::from_data(data, format)?;
+            workbook.path = Some(path.to_string());
+            return Ok(workbook);
+        }
+
+        let sheets = WorkbookFormat::open_or_detect(format, || {
+            File::open(path)
+                .map(BufReader::new)
+                .map_err(CalamineCrateError::Io)
+        });
+        let sheets = SheetsEnum::File(sheets.map_err(Error::Calamine).map_err(err_to_py)?);
         let sheet_names = sheets.sheet_names().to_owned();
         let sheets_metadata = sheets.sheets_metadata().to_owned();
 
@@ -187,20 +783,130 @@ impl CalamineWorkbook {
             sheets,
             sheets_metadata,
             sheet_names,
+            defined_names: None,
+            empty_value: None,
+            dates: DatesMode::Infer,
         })
     }
 
-    fn get_sheet_by_name(&mut self, name: &str) -> PyResult<CalamineSheet> {
-        let range = self.sheets.worksheet_range(name).map_err(err_to_py)?;
-        Ok(CalamineSheet::new(name.to_owned(), range))
+    fn get_sheet_by_name(
+        &mut self,
+        name: &str,
+        formulas: bool,
+        merged_cells: bool,
+        nrows: Option<u32>,
+        skiprows: u32,
+        number_formats: bool,
+    ) -> PyResult<CalamineSheet> {
+        let range = if nrows.is_none() && skiprows == 0 {
+            self.sheets.worksheet_range(name)
+        } else {
+            self.sheets.worksheet_range_rows(name, skiprows, nrows)
+        }
+        .map_err(err_to_py)?;
+        let mut sheet = CalamineSheet::new(name.to_owned(), range);
+        if formulas {
+            let formulas = self.sheets.worksheet_formula(name).map_err(err_to_py)?;
+            sheet = sheet.with_formulas(formulas);
+        }
+        if merged_cells {
+            let merged_cells = self
+                .sheets
+                .worksheet_merge_cells(name, self.path.as_deref())
+                .map_err(err_to_py)?;
+            sheet = sheet.with_merged_cells(merged_cells);
+        }
+        if number_formats {
+            let number_formats = self
+                .sheets
+                .number_formats(name, self.path.as_deref())
+                .map_err(err_to_py)?;
+            sheet = sheet.with_number_formats(number_formats);
+        }
+        Ok(sheet)
     }
 
-    fn get_sheet_by_index(&mut self, index: usize) -> PyResult<CalamineSheet> {
+    fn get_sheet_by_index(
+        &mut self,
+        index: usize,
+        formulas: bool,
+        merged_cells: bool,
+        nrows: Option<u32>,
+        skiprows: u32,
+        number_formats: bool,
+    ) -> PyResult<CalamineSheet> {
         let name = self
             .sheet_names
             .get(index)
             .ok_or_else(|| WorksheetNotFound::new_err(format!("Worksheet '{}' not found", index)))?
             .to_string();
-        self.get_sheet_by_name(&name)
+        self.get_sheet_by_name(
+            &name,
+            formulas,
+            merged_cells,
+            nrows,
+            skiprows,
+            number_formats,
+        )
+    }
+
+    /// Defined names with scopes, `None` if scopes are unknown.
+    fn load_defined_names(&mut self) -> Result<Option<&Vec<DefinedName>>, Error> {
+        if self.defined_names.is_none() {
+            self.defined_names = self.sheets.defined_names(self.path.as_deref())?;
+        }
+        Ok(self.defined_names.as_ref())
+    }
+
+    fn get_named_range(&mut self, name: &str) -> PyResult<CalamineSheet> {
+        let reference = match self.load_defined_names().map_err(err_to_py)? {
+            // workbook-level name before sheet-scoped ones
+            Some(names) => names
+                .iter()
+                .filter(|defined_name| defined_name.0 == name)
+                .min_by_key(|defined_name| defined_name.2.is_some())
+                .map(|defined_name| defined_name.1.clone()),
+            None => self
+                .sheets
+                .names()
+                .map_err(err_to_py)?
+                .iter()
+                .find(|defined_name| defined_name.0 == name)
+                .map(|defined_name| defined_name.1.clone()),
+        }
+        .ok_or_else(|| CalamineError::new_err(format!("Defined name '{}' not found", name)))?;
+        let (sheet_name, (start, end)) = parse_sheet_reference(&reference).ok_or_else(|| {
+            CalamineError::new_err(format!(
+                "Defined name '{}' is not a reference to cells: {}",
+                name, reference
+            ))
+        })?;
+
+        let nrows = end.0.saturating_sub(start.0).saturating_add(1);
+        let range = self
+            .sheets
+            .worksheet_range_rows(&sheet_name, start.0, Some(nrows))
+            .map_err(err_to_py)?;
+        // whole rows and columns are limited by data of sheet,
+        // explicit bounds are kept with empty cells outside of data
+        let data_end = range.end().filter(|_| !range.is_empty());
+        let limit = |bound: u32, data_end: Option<u32>| match bound {
+            u32::MAX => data_end,
+            bound => Some(bound),
+        };
+        let range = match (
+            limit(end.0, data_end.map(|end| end.0)),
+            limit(end.1, data_end.map(|end| end.1)),
+        ) {
+            (Some(row), Some(col)) if start.0 <= row && start.1 <= col => {
+                if data_end.is_some() {
+                    range.range(start, (row, col))
+                } else {
+                    Range::new(start, (row, col))
+                }
+            }
+            _ => Range::empty(),
+        };
+        Ok(CalamineSheet::new(sheet_name, range))
     }
 }
@@ -1,9 +1,13 @@
-use calamine::{Error as CalamineCrateError, OdsError, XlsError, XlsbError, XlsxError};
+use std::collections::HashSet;
+
+use arrow_schema::ArrowError;
+use calamine::{Data, Error as CalamineCrateError, OdsError, XlsError, XlsbError, XlsxError};
 use pyo3::exceptions::PyIOError;
 use pyo3::PyErr;
 
 use crate::{
-    CalamineError, Error, PasswordError, WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
+    CalamineError, CellValue, Error, PasswordError, WorkbookClosed, WorksheetNotFound, XmlError,
+    ZipError,
 };
 
 pub fn err_to_py(e: Error) -> PyErr {
@@ -53,3 +57,135 @@ pub fn err_to_py(e: Error) -> PyErr {
         Error::WorkbookClosed => WorkbookClosed::new_err("".to_string()),
     }
 }
+
+pub fn arrow_err_to_py(e: ArrowError) -> PyErr {
+    CalamineError::new_err(e.to_string())
+}
+
+/// Makes column names from the header rows, like pandas does:
+/// names from several rows are joined by `_`, blank names become `Unnamed: {index}`,
+/// duplicates get `.1`, `.2`, ... suffixes.
+pub fn header_names(header: &[&[Data]], width: usize) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(width);
+    let mut seen = HashSet::with_capacity(width);
+    for index in 0..width {
+        let parts: Vec<String> = header
+            .iter()
+            .filter_map(|row| row.get(index))
+            .map(|value| CellValue::from(value).to_string())
+            .filter(|part| !part.is_empty())
+            .collect();
+        let name = if parts.is_empty() {
+            format!("Unnamed: {}", index)
+        } else {
+            parts.join("_")
+        };
+
+        let mut unique = name.clone();
+        let mut counter = 0;
+        while seen.contains(&unique) {
+            counter += 1;
+            unique = format!("{}.{}", name, counter);
+        }
+        seen.insert(unique.clone());
+        names.push(unique);
+    }
+    names
+}
+
+/// Zero-based (start, end) positions of cells area.
+pub type Area = ((u32, u32), (u32, u32));
+
+/// Converts column name like `AB` or `$AB` to zero-based index.
+pub fn column_index(name: &str) -> Option<u32> {
+    let name = name.strip_prefix('$').unwrap_or(name);
+    if name.is_empty() || name.len() > 3 {
+        return None;
+    }
+    name.bytes()
+        .try_fold(0u32, |index, c| {
+            c.is_ascii_alphabetic()
+                .then(|| index * 26 + (c.to_ascii_uppercase() - b'A') as u32 + 1)
+        })
+        .map(|index| index - 1)
+}
+
+/// Converts zero-based column index to name like `AB`.
+pub fn column_name(index: u32) -> String {
+    let mut name = Vec::new();
+    let mut index = index as u64 + 1;
+    while index > 0 {
+        name.push(b'A' + ((index - 1) % 26) as u8);
+        index = (index - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// Converts row number like `7` or `$7` to zero-based index.
+fn row_index(number: &str) -> Option<u32> {
+    let number = number.strip_prefix('$').unwrap_or(number);
+    if !number.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    number.parse::<u32>().ok()?.checked_sub(1)
+}
+
+/// Parses cell reference like `B7` or `$B$7` to zero-based (row, col).
+pub fn parse_cell(reference: &str) -> Option<(u32, u32)> {
+    let split = reference
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '$' || c.is_ascii_digit())?
+        .0;
+    let (column, row) = reference.split_at(split);
+    Some((row_index(row)?, column_index(column)?))
+}
+
+/// Parses area like `A1`, `A1:C10`, `A:C` or `1:3` to zero-based (start, end),
+/// whole columns and rows end at `u32::MAX`.
+pub fn parse_area(reference: &str) -> Option<Area> {
+    let (first, last) = reference.split_once(':').unwrap_or((reference, reference));
+    let (start, end) = if let (Some(start), Some(end)) = (parse_cell(first), parse_cell(last)) {
+        (start, end)
+    } else if let (Some(start), Some(end)) = (column_index(first), column_index(last)) {
+        ((0, start), (u32::MAX, end))
+    } else if let (Some(start), Some(end)) = (row_index(first), row_index(last)) {
+        ((start, 0), (end, u32::MAX))
+    } else {
+        return None;
+    };
+    Some((
+        (start.0.min(end.0), start.1.min(end.1)),
+        (start.0.max(end.0), start.1.max(end.1)),
+    ))
+}
+
+/// Removes `$` and quotes from sheet name in reference, `'It''s'` becomes `It's`.
+fn unquote_sheet_name(name: &str) -> String {
+    let name = name.strip_prefix('$').unwrap_or(name);
+    match name.strip_prefix('\'').and_then(|n| n.strip_suffix('\'')) {
+        Some(quoted) => quoted.replace("''", "'"),
+        None => name.to_string(),
+    }
+}
+
+/// Parses reference to cells of sheet, Excel `Sheet1!$A$1:$C$10` or ODS `$Sheet1.$A$1:.$C$10`.
+/// Returns `None` for other formulas, e.g. several areas or constants.
+pub fn parse_sheet_reference(reference: &str) -> Option<(String, Area)> {
+    let reference = reference.trim().trim_start_matches('=');
+    if let Some((sheet, area)) = reference.rsplit_once('!') {
+        return Some((unquote_sheet_name(sheet), parse_area(area)?));
+    }
+
+    let (first, last) = reference.split_once(':').unwrap_or((reference, reference));
+    let (sheet, first) = first.rsplit_once('.')?;
+    let last = last.rsplit_once('.').map_or(last, |(_, cell)| cell);
+    if sheet.is_empty() {
+        return None;
+    }
+    Some((
+        unquote_sheet_name(sheet),
+        parse_area(&format!("{}:{}", first, last))?,
+    ))
+}
@@ -0,0 +1,177 @@
+//! Parts of xlsb, which calamine doesn't expose.
+use std::io::{BufReader, Read, Seek};
+
+use calamine::{Dimensions, XlsbError};
+use quick_xml::events::Event;
+use quick_xml::name::QName;
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+/// BrtBundleSh [MS-XLSB] 2.4.304
+const BRT_BUNDLE_SH: u16 = 0x009C;
+/// BrtMergeCell [MS-XLSB] 2.4.655
+const BRT_MERGE_CELL: u16 = 0x00B0;
+
+fn read_u32(data: &[u8], i: usize) -> Option<u32> {
+    data.get(i..i + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Reads XLWideString [MS-XLSB] 2.5.168 at `i`, returns it and the offset after it.
+fn read_wide_string(data: &[u8], i: usize) -> Option<(String, usize)> {
+    let len = read_u32(data, i)? as usize;
+    let bytes = data.get(i + 4..i + 4 + len.checked_mul(2)?)?;
+    let chars: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    Some((String::from_utf16_lossy(&chars), i + 4 + len * 2))
+}
+
+/// Records of binary part, as (type, data).
+struct Records<R> {
+    reader: R,
+    data: Vec<u8>,
+}
+
+impl<R: Read> Records<R> {
+    fn new(reader: R) -> Self {
+        Records {
+            reader,
+            data: Vec::new(),
+        }
+    }
+
+    fn read_u8(&mut self) -> std::io::Result<u8> {
+        let mut b = [0];
+        self.reader.read_exact(&mut b)?;
+        Ok(b[0])
+    }
+
+    /// Returns type of the next record and fills its data, `None` at the end of part.
+    fn next_record(&mut self) -> Result<Option<u16>, XlsbError> {
+        // type and length are variable size, 7 bits in each byte
+        let b = match self.read_u8() {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(XlsbError::Io(e)),
+        };
+        let typ = if b & 0x80 != 0 {
+            u16::from(b & 0x7F) + (u16::from(self.read_u8()? & 0x7F) << 7)
+        } else {
+            u16::from(b)
+        };
+        let mut len = 0;
+        for i in 0..4 {
+            let b = self.read_u8()?;
+            len += usize::from(b & 0x7F) << (7 * i);
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        self.data.clear();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.data)?;
+        if self.data.len() != len {
+            return Err(XlsbError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        Ok(Some(typ))
+    }
+}
+
+/// Returns path of worksheet `name` in the archive, resolved via workbook relationships.
+fn worksheet_path<RS: Read + Seek>(
+    zip: &mut ZipArchive<RS>,
+    name: &str,
+) -> Result<String, XlsbError> {
+    let mut relationship = None;
+    let mut records = Records::new(BufReader::new(zip.by_name("xl/workbook.bin")?));
+    while let Some(typ) = records.next_record()? {
+        if typ != BRT_BUNDLE_SH {
+            continue;
+        }
+        // hsState, iTabID, strRelID (nullable), strName
+        let data = &records.data;
+        if read_u32(data, 8) == Some(0xFFFF_FFFF) {
+            continue;
+        }
+        let Some((id, next)) = read_wide_string(data, 8) else {
+            continue;
+        };
+        if read_wide_string(data, next).is_some_and(|(sheet, _)| sheet == name) {
+            relationship = Some(id);
+            break;
+        }
+    }
+    drop(records);
+    let relationship =
+        relationship.ok_or_else(|| XlsbError::WorksheetNotFound(name.to_string()))?;
+
+    let mut xml =
+        XmlReader::from_reader(BufReader::new(zip.by_name("xl/_rels/workbook.bin.rels")?));
+    xml.expand_empty_elements(true);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"Relationship" => {
+                let mut id = None;
+                let mut target = None;
+                for attribute in e.attributes() {
+                    let attribute = attribute.map_err(XlsbError::XmlAttr)?;
+                    if attribute.key == QName(b"Id") {
+                        id = Some(attribute.decode_and_unescape_value(&xml)?.to_string());
+                    } else if attribute.key == QName(b"Target") {
+                        target = Some(attribute.decode_and_unescape_value(&xml)?.to_string());
+                    }
+                }
+                if id.as_deref() == Some(relationship.as_str()) {
+                    let target = target.unwrap_or_default();
+                    return Ok(match target.strip_prefix('/') {
+                        Some(absolute) => absolute.to_string(),
+                        None => format!("xl/{}", target),
+                    });
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Err(XlsbError::WorksheetNotFound(name.to_string()))
+}
+
+/// Returns merged regions of worksheet `name`.
+pub fn worksheet_merge_cells<RS: Read + Seek>(
+    reader: RS,
+    name: &str,
+) -> Result<Vec<Dimensions>, XlsbError> {
+    let mut zip = ZipArchive::new(reader)?;
+    let path = worksheet_path(&mut zip, name)?;
+    // chart and dialog sheets don't have cells
+    if path.split('/').nth(1) != Some("worksheets") {
+        return Ok(Vec::new());
+    }
+    let mut records = Records::new(BufReader::new(zip.by_name(&path)?));
+
+    let mut merged = Vec::new();
+    while let Some(typ) = records.next_record()? {
+        if typ != BRT_MERGE_CELL {
+            continue;
+        }
+        // RfX: rwFirst, rwLast, colFirst, colLast
+        let data = &records.data;
+        if let (Some(first_row), Some(last_row), Some(first_col), Some(last_col)) = (
+            read_u32(data, 0),
+            read_u32(data, 4),
+            read_u32(data, 8),
+            read_u32(data, 12),
+        ) {
+            merged.push(Dimensions {
+                start: (first_row, first_col),
+                end: (last_row, last_col),
+            });
+        }
+    }
+    Ok(merged)
+}
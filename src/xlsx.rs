@@ -0,0 +1,302 @@
+//! Parts of xlsx, which calamine doesn't expose.
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use calamine::{Cell, Range, XlsxError};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::events::Event;
+use quick_xml::name::QName;
+use quick_xml::Reader as XmlReader;
+use zip::read::ZipFile;
+use zip::ZipArchive;
+
+use crate::utils::parse_cell;
+
+fn get_attribute<B: BufRead>(
+    xml: &XmlReader<B>,
+    attributes: Attributes<'_>,
+    key: &[u8],
+) -> Result<Option<String>, XlsxError> {
+    for attribute in attributes {
+        let attribute = attribute.map_err(XlsxError::XmlAttr)?;
+        if attribute.key == QName(key) {
+            return Ok(Some(attribute.decode_and_unescape_value(xml)?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn open_xml<'a, RS: Read + Seek>(
+    zip: &'a mut ZipArchive<RS>,
+    path: &str,
+) -> Result<XmlReader<BufReader<ZipFile<'a>>>, XlsxError> {
+    let file = match zip.by_name(path) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(XlsxError::FileNotFound(path.to_string()))
+        }
+        Err(e) => return Err(XlsxError::Zip(e)),
+    };
+    let mut xml = XmlReader::from_reader(BufReader::new(file));
+    xml.expand_empty_elements(true);
+    Ok(xml)
+}
+
+/// Returns defined names with their scope (name of sheet for `localSheetId`),
+/// in the same order as `calamine::Reader::defined_names`.
+pub fn defined_names_scopes<RS: Read + Seek>(
+    reader: RS,
+) -> Result<Vec<(String, Option<String>)>, XlsxError> {
+    let mut zip = ZipArchive::new(reader)?;
+    let mut xml = open_xml(&mut zip, "xl/workbook.xml")?;
+
+    let mut sheets = Vec::new();
+    let mut names = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"sheet" => {
+                sheets.push(get_attribute(&xml, e.attributes(), b"name")?.unwrap_or_default());
+            }
+            Event::Start(ref e) if e.local_name().as_ref() == b"definedName" => {
+                // names without `name` attribute are skipped by calamine too
+                if let Some(name) = get_attribute(&xml, e.attributes(), b"name")? {
+                    let sheet_id = get_attribute(&xml, e.attributes(), b"localSheetId")?
+                        .and_then(|id| id.parse::<usize>().ok());
+                    names.push((name, sheet_id));
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    Ok(names
+        .into_iter()
+        .map(|(name, sheet_id)| (name, sheet_id.and_then(|id| sheets.get(id).cloned())))
+        .collect())
+}
+
+/// Built-in number formats (ECMA-376, 18.8.30), locale-dependent ones as in en-US Excel.
+fn builtin_number_format(id: u32) -> Option<&'static str> {
+    let code = match id {
+        0 => "General",
+        1 => "0",
+        2 => "0.00",
+        3 => "#,##0",
+        4 => "#,##0.00",
+        5 => "\"$\"#,##0_);\\(\"$\"#,##0\\)",
+        6 => "\"$\"#,##0_);[Red]\\(\"$\"#,##0\\)",
+        7 => "\"$\"#,##0.00_);\\(\"$\"#,##0.00\\)",
+        8 => "\"$\"#,##0.00_);[Red]\\(\"$\"#,##0.00\\)",
+        9 => "0%",
+        10 => "0.00%",
+        11 => "0.00E+00",
+        12 => "# ?/?",
+        13 => "# ??/??",
+        14 => "m/d/yyyy",
+        15 => "d-mmm-yy",
+        16 => "d-mmm",
+        17 => "mmm-yy",
+        18 => "h:mm AM/PM",
+        19 => "h:mm:ss AM/PM",
+        20 => "h:mm",
+        21 => "h:mm:ss",
+        22 => "m/d/yyyy h:mm",
+        37 => "#,##0_);\\(#,##0\\)",
+        38 => "#,##0_);[Red]\\(#,##0\\)",
+        39 => "#,##0.00_);\\(#,##0.00\\)",
+        40 => "#,##0.00_);[Red]\\(#,##0.00\\)",
+        41 => "_(* #,##0_);_(* \\(#,##0\\);_(* \"-\"_);_(@_)",
+        42 => "_(\"$\"* #,##0_);_(\"$\"* \\(#,##0\\);_(\"$\"* \"-\"_);_(@_)",
+        43 => "_(* #,##0.00_);_(* \\(#,##0.00\\);_(* \"-\"??_);_(@_)",
+        44 => "_(\"$\"* #,##0.00_);_(\"$\"* \\(#,##0.00\\);_(\"$\"* \"-\"??_);_(@_)",
+        45 => "mm:ss",
+        46 => "[h]:mm:ss",
+        47 => "mm:ss.0",
+        48 => "##0.0E+0",
+        49 => "@",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Returns path of worksheet `name` in the archive, resolved via workbook relationships.
+fn worksheet_path<RS: Read + Seek>(
+    zip: &mut ZipArchive<RS>,
+    name: &str,
+) -> Result<String, XlsxError> {
+    let mut relationship = None;
+    let mut buf = Vec::new();
+    let mut xml = open_xml(zip, "xl/workbook.xml")?;
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"sheet" => {
+                if get_attribute(&xml, e.attributes(), b"name")?.as_deref() != Some(name) {
+                    continue;
+                }
+                // `r:id`, the prefix of relationships namespace may differ
+                for attribute in e.attributes() {
+                    let attribute = attribute.map_err(XlsxError::XmlAttr)?;
+                    if attribute.key.local_name().as_ref() == b"id"
+                        && attribute.key.prefix().is_some()
+                    {
+                        relationship = Some(attribute.decode_and_unescape_value(&xml)?.to_string());
+                    }
+                }
+                break;
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    drop(xml);
+    let relationship =
+        relationship.ok_or_else(|| XlsxError::WorksheetNotFound(name.to_string()))?;
+
+    let mut xml = open_xml(zip, "xl/_rels/workbook.xml.rels")?;
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"Relationship" => {
+                if get_attribute(&xml, e.attributes(), b"Id")?.as_deref()
+                    != Some(relationship.as_str())
+                {
+                    continue;
+                }
+                let target = get_attribute(&xml, e.attributes(), b"Target")?.unwrap_or_default();
+                return Ok(match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                });
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Err(XlsxError::WorksheetNotFound(name.to_string()))
+}
+
+/// Returns number format codes of cell styles (`cellXfs`), by style index.
+fn cell_number_formats<RS: Read + Seek>(
+    zip: &mut ZipArchive<RS>,
+) -> Result<Vec<String>, XlsxError> {
+    let mut xml = match open_xml(zip, "xl/styles.xml") {
+        Ok(xml) => xml,
+        // styles are optional, all cells are `General`
+        Err(XlsxError::FileNotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut custom = HashMap::new();
+    let mut ids = Vec::new();
+    let mut in_cell_xfs = false;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"numFmt" => {
+                let id = get_attribute(&xml, e.attributes(), b"numFmtId")?
+                    .and_then(|id| id.parse::<u32>().ok());
+                let code = get_attribute(&xml, e.attributes(), b"formatCode")?;
+                if let (Some(id), Some(code)) = (id, code) {
+                    custom.insert(id, code);
+                }
+            }
+            Event::Start(ref e) if e.local_name().as_ref() == b"cellXfs" => in_cell_xfs = true,
+            Event::End(ref e) if e.local_name().as_ref() == b"cellXfs" => in_cell_xfs = false,
+            Event::Start(ref e) if in_cell_xfs && e.local_name().as_ref() == b"xf" => {
+                ids.push(
+                    get_attribute(&xml, e.attributes(), b"numFmtId")?
+                        .and_then(|id| id.parse::<u32>().ok())
+                        .unwrap_or_default(),
+                );
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(ids
+        .into_iter()
+        .map(|id| match custom.get(&id) {
+            Some(code) => code.clone(),
+            None => builtin_number_format(id).unwrap_or("General").to_string(),
+        })
+        .collect())
+}
+
+/// Number format codes of worksheet cells, each code is stored once per cell style.
+#[derive(Debug)]
+pub struct NumberFormats {
+    /// Codes by style index.
+    codes: Vec<String>,
+    /// Style index + 1 of cells, 0 for cells with `General` format.
+    styles: Range<usize>,
+}
+
+impl NumberFormats {
+    /// Code at absolute `position`, `None` for `General` format.
+    pub fn get(&self, position: (u32, u32)) -> Option<&str> {
+        match self.styles.get_value(position) {
+            Some(&style) if style > 0 => Some(&self.codes[style - 1]),
+            _ => None,
+        }
+    }
+}
+
+/// Returns number format codes of cells of worksheet `name`.
+pub fn worksheet_number_formats<RS: Read + Seek>(
+    reader: RS,
+    name: &str,
+) -> Result<NumberFormats, XlsxError> {
+    let mut zip = ZipArchive::new(reader)?;
+    let codes = cell_number_formats(&mut zip)?;
+    let path = worksheet_path(&mut zip, name)?;
+    let mut xml = open_xml(&mut zip, &path)?;
+
+    let mut cells = Vec::new();
+    // positions of rows and cells without `r` attribute follow the previous ones
+    let mut row = 0;
+    let mut col = 0;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf)? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"row" => {
+                if let Some(r) =
+                    get_attribute(&xml, e.attributes(), b"r")?.and_then(|r| r.parse::<u32>().ok())
+                {
+                    row = r.saturating_sub(1);
+                }
+                col = 0;
+            }
+            Event::End(ref e) if e.local_name().as_ref() == b"row" => row += 1,
+            Event::Start(ref e) if e.local_name().as_ref() == b"c" => {
+                if let Some(position) =
+                    get_attribute(&xml, e.attributes(), b"r")?.and_then(|r| parse_cell(&r))
+                {
+                    (row, col) = position;
+                }
+                let style = get_attribute(&xml, e.attributes(), b"s")?
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .filter(|&s| {
+                        codes
+                            .get(s)
+                            .is_some_and(|code| !code.eq_ignore_ascii_case("general"))
+                    });
+                if let Some(style) = style {
+                    cells.push(Cell::new((row, col), style + 1));
+                }
+                col += 1;
+            }
+            Event::End(ref e) if e.local_name().as_ref() == b"sheetData" => break,
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(NumberFormats {
+        codes,
+        styles: Range::from_sparse(cells),
+    })
+}
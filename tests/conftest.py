@@ -17,11 +17,9 @@ def expected_df_ods():
                 False,
                 pd.Timestamp("2010-10-10"),
                 datetime(2010, 10, 10, 10, 10, 10),
-                time(10, 10, 10),
-                time(10, 10, 10, 100000),
-                # duration (255:10:10) isn't supported
-                # see https://github.com/tafia/calamine/pull/288 and https://github.com/chronotope/chrono/issues/579
-                "PT255H10M10S",
+                pd.Timedelta(hours=10, minutes=10, seconds=10),
+                pd.Timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+                pd.Timedelta(hours=255, minutes=10, seconds=10),
             ],
         ],
         columns=[
@@ -1,14 +1,24 @@
+import re
+import zipfile
 from datetime import date, datetime, time, timedelta
+from decimal import Decimal
 from io import BytesIO
 from pathlib import Path
 
 import pytest
 from python_calamine import (
+    CalamineError,
     CalamineWorkbook,
+    CellError,
+    CellErrorTypeEnum,
+    CellValueError,
+    DetectedFormat,
     PasswordError,
     WorkbookClosed,
     WorksheetNotFound,
     ZipError,
+    detect_format,
+    load_workbook,
 )
 
 PATH = Path(__file__).parent / "data"
@@ -26,11 +36,9 @@ def test_ods_read():
             False,
             date(2010, 10, 10),
             datetime(2010, 10, 10, 10, 10, 10),
-            time(10, 10, 10),
-            time(10, 10, 10, 100000),
-            # duration (255:10:10) isn't supported
-            # see https://github.com/tafia/calamine/pull/288 and https://github.com/chronotope/chrono/issues/579
-            "PT255H10M10S",
+            timedelta(hours=10, minutes=10, seconds=10),
+            timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+            timedelta(hours=255, minutes=10, seconds=10),
         ],
     ]
 
@@ -48,11 +56,9 @@ def test_ods_read():
             False,
             date(2010, 10, 10),
             datetime(2010, 10, 10, 10, 10, 10),
-            time(10, 10, 10),
-            time(10, 10, 10, 100000),
-            # duration (255:10:10) isn't supported
-            # see https://github.com/tafia/calamine/pull/288 and https://github.com/chronotope/chrono/issues/579
-            "PT255H10M10S",
+            timedelta(hours=10, minutes=10, seconds=10),
+            timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+            timedelta(hours=255, minutes=10, seconds=10),
         ],
     ]
     assert data_skipped == reader.get_sheet_by_index(0).to_python()
@@ -208,6 +214,7 @@ def test_xlsx_iter_rows():
 
     assert names == reader.sheet_names
     assert data == list(reader.get_sheet_by_index(0).iter_rows())
+    assert [] == list(reader.get_sheet_by_index(1).iter_rows())
 
 
 def test_nrows():
@@ -248,6 +255,1200 @@ def test_nrows():
     ]
 
 
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.xlsb",
+        PATH / "base.xlsx",
+    ],
+)
+def test_get_sheet_nrows(path):
+    reader = CalamineWorkbook.from_object(path)
+
+    sheet = reader.get_sheet_by_name("Sheet3", nrows=2)
+    assert sheet.to_python() == [
+        ["line1", "line1", "line1"],
+    ]
+    assert sheet.start == (1, 1)
+
+    sheet = reader.get_sheet_by_index(2, skiprows=2, nrows=1)
+    assert sheet.to_python() == [
+        ["line2", "line2", "line2"],
+    ]
+
+    sheet = reader.get_sheet_by_name("Sheet3", skiprows=2)
+    assert sheet.to_python() == [
+        ["line2", "line2", "line2"],
+        ["line3", "line3", "line3"],
+    ]
+
+    sheet = reader.get_sheet_by_name("Sheet3", nrows=0)
+    assert sheet.to_python() == []
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.ods",
+        PATH / "base.xls",
+    ],
+)
+def test_get_sheet_nrows_not_streaming(path):
+    reader = CalamineWorkbook.from_object(path)
+    expected = reader.get_sheet_by_name("Sheet1").to_python()
+
+    assert reader.get_sheet_by_name("Sheet1", nrows=1).to_python() == []
+    assert reader.get_sheet_by_name("Sheet1", nrows=2).to_python() == expected
+    assert reader.get_sheet_by_name("Sheet1", skiprows=1).to_python() == expected
+
+
+def test_defined_names():
+    reader = CalamineWorkbook.from_object(PATH / "defined_names.xlsx")
+
+    assert reader.defined_names == [
+        ("Prices", "Data!$A$2:$B$3", None),
+        ("Header", "Data!$A$1:$C$1", None),
+        ("Quoted", "'My Sheet'!$B$2", None),
+        ("Constant", "42", None),
+        ("Columns", "Data!$B:$C", None),
+        ("Prices", "'My Sheet'!$A$1:$B$2", "My Sheet"),
+    ]
+
+    with open(PATH / "defined_names.xlsx", "rb") as f:
+        assert CalamineWorkbook.from_filelike(f).defined_names == reader.defined_names
+
+    assert CalamineWorkbook.from_object(PATH / "base.xlsx").defined_names == []
+
+
+def test_get_named_range():
+    reader = CalamineWorkbook.from_object(PATH / "defined_names.xlsx")
+
+    sheet = reader.get_named_range("Prices")
+    assert sheet.name == "Data"
+    assert sheet.start == (1, 0)
+    assert sheet.to_python() == [["apple", 1.5], ["pear", 2.5]]
+
+    assert reader.get_named_range("Header").to_python() == [["name", "price", "qty"]]
+    assert reader.get_named_range("Quoted").to_python() == [["d"]]
+    assert reader.get_named_range("Columns").to_python() == [
+        ["price", "qty"],
+        [1.5, 10],
+        [2.5, 20],
+        [3.5, 30],
+    ]
+
+    with pytest.raises(CalamineError):
+        reader.get_named_range("Constant")
+
+    with pytest.raises(CalamineError):
+        reader.get_named_range("NotFound")
+
+
+def test_get_named_range_ods():
+    reader = CalamineWorkbook.from_object(PATH / "defined_names.ods")
+
+    # scopes of ods names are unknown
+    assert reader.defined_names == [
+        ("Prices", "$Data.$A$2:.$B$3", None),
+        ("Padded", "$Data.$B$4:.$D$5", None),
+    ]
+
+    assert reader.get_named_range("Prices").to_python() == [
+        ["apple", 1.5],
+        ["pear", 2.5],
+    ]
+    # explicit bounds outside of data are filled with empty cells
+    assert reader.get_named_range("Padded").to_python() == [
+        [3.5, 30, ""],
+        ["", "", ""],
+    ]
+
+
+def test_tables():
+    reader = CalamineWorkbook.from_object(PATH / "tables.xlsx")
+
+    assert reader.table_names == ["Fruits", "Cities"]
+    assert reader.table_names_in_sheet("Sheet1") == ["Fruits"]
+    assert reader.table_names_in_sheet("Sheet2") == ["Cities"]
+
+    table = reader.get_table("Fruits")
+    assert table.name == "Fruits"
+    assert table.sheet_name == "Sheet1"
+    assert table.columns == ["name", "price", "qty"]
+    assert table.data.start == (3, 1)
+    assert table.data.to_python() == [
+        ["apple", 1.5, 10],
+        ["pear", 2.5, 20],
+        ["plum", 3.5, ""],
+    ]
+
+    with pytest.raises(CalamineError):
+        reader.get_table("NotFound")
+
+
+def test_tables_not_supported():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsb")
+
+    with pytest.raises(CalamineError):
+        reader.table_names
+
+    with pytest.raises(CalamineError):
+        reader.get_table("Table1")
+
+
+def test_cell():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.cell(1, 1) == "line1"
+    assert sheet.cell(0, 0) == ""
+    assert sheet.cell(0, 0, relative=True) == "line1"
+    assert sheet.cell(100, 100) == ""
+    assert sheet["C3"] == "line2"
+    assert sheet[(3, 3)] == "line3"
+
+    with pytest.raises(ValueError):
+        sheet["3C"]
+
+
+def test_range():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    sub_range = sheet.range("C2:D3")
+    assert sub_range.start == (1, 2)
+    assert sub_range.end == (2, 3)
+    assert (sub_range.height, sub_range.width) == (2, 2)
+    assert sub_range.to_python() == [["line1", "line1"], ["line2", "line2"]]
+    assert sub_range.cell(0, 0, relative=True) == "line1"
+    assert sub_range["D3"] == "line2"
+
+    assert sheet.range((1, 2), (2, 3)).to_python() == sub_range.to_python()
+    assert sheet.range((0, 1), (1, 2), relative=True).to_python() == sub_range.to_python()
+    assert sheet.range("C:C").to_python() == [["line1"], ["line2"], ["line3"]]
+    assert sheet.range("3:3").to_python() == [["line2", "line2", "line2"]]
+    # explicit ranges are clipped by data
+    assert sheet.range("A1:XFD1048576").end == (3, 3)
+    assert sheet.range("Z100:Z200").to_python() == []
+
+    empty = reader.get_sheet_by_name("Sheet2")
+    for reference in ["A1:B2", "A:XFD", "1:1048576"]:
+        assert empty.range(reference).to_python() == []
+        assert empty.range(reference).start is None
+
+    with pytest.raises(IndexError):
+        sub_range["A1"]
+
+    with pytest.raises(IndexError):
+        sub_range.range("A1:B2")
+
+    with pytest.raises(ValueError):
+        sheet.range("A1:")
+
+    with pytest.raises(TypeError):
+        sheet.range((0, 0))
+
+
+def test_to_records():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.to_records() == [
+        {"line1": "line2", "line1.1": "line2", "line1.2": "line2"},
+        {"line1": "line3", "line1.1": "line3", "line1.2": "line3"},
+    ]
+    assert sheet.to_records(header_row=1, nrows=1) == [
+        {"line2": "line3", "line2.1": "line3", "line2.2": "line3"},
+    ]
+    assert sheet.to_records(header_row=5) == []
+    assert list(sheet.iter_records()) == sheet.to_records()
+    assert list(sheet.iter_records(nrows=1)) == sheet.to_records(nrows=1)
+
+    records = sheet.to_records(record_type="namedtuple")
+    assert [tuple(record) for record in records] == [
+        ("line2", "line2", "line2"),
+        ("line3", "line3", "line3"),
+    ]
+    assert records[0]._fields == ("line1", "_1", "_2")
+
+    with pytest.raises(ValueError):
+        sheet.to_records(record_type="list")
+
+
+def test_to_records_multirow_header():
+    reader = CalamineWorkbook.from_object(PATH / "merged_cells.xlsx")
+    sheet = reader.get_sheet_by_index(0, merged_cells=True)
+
+    assert sheet.to_records(header_row=[0, 1]) == [
+        {"Q1_Jan": 1, "Feb": 2, "Q2_Apr": 3, "May": 4},
+        {"Q1_Jan": "Total", "Feb": "", "Q2_Apr": 10, "May": ""},
+    ]
+    assert sheet.to_records(header_row=[0, 1], fill_merged=True)[0] == {
+        "Q1_Jan": 1,
+        "Q1_Feb": 2,
+        "Q2_Apr": 3,
+        "Q2_May": 4,
+    }
+
+
+def test_to_columns():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.to_columns() == [
+        ["line1", "line2", "line3"],
+        ["line1", "line2", "line3"],
+        ["line1", "line2", "line3"],
+    ]
+    assert sheet.to_columns(nrows=1) == [["line1"], ["line1"], ["line1"]]
+    assert sheet.to_columns(header_row=0) == {
+        "line1": ["line2", "line3"],
+        "line1.1": ["line2", "line3"],
+        "line1.2": ["line2", "line3"],
+    }
+    assert sheet.to_columns(header_row=[0, 1], nrows=5) == {
+        "line1_line2": ["line3"],
+        "line1_line2.1": ["line3"],
+        "line1_line2.2": ["line3"],
+    }
+
+
+def test_to_numpy():
+    np = pytest.importorskip("numpy")
+
+    reader = CalamineWorkbook.from_object(PATH / "numeric.xlsx")
+    sheet = reader.get_sheet_by_index(0)
+
+    array = sheet.to_numpy(usecols="A:B")
+    assert array.dtype == np.float64
+    assert array.shape == (4, 2)
+    np.testing.assert_array_equal(
+        array, [[1.0, 10.0], [2.5, 20.0], [np.nan, 30.0], [np.nan, np.nan]]
+    )
+
+    assert sheet.to_numpy(usecols=[0], nrows=2, dtype="float32").tolist() == [
+        [1.0],
+        [2.5],
+    ]
+
+    masked = sheet.to_numpy(usecols=[1], dtype="int64")
+    assert isinstance(masked, np.ma.MaskedArray)
+    assert masked.tolist() == [[10], [20], [30], [None]]
+
+    dates = sheet.to_numpy(usecols="C")
+    assert dates.dtype == np.dtype("datetime64[us]")
+    np.testing.assert_array_equal(
+        dates[:, 0],
+        np.array(
+            ["2020-01-01", "2020-01-02T12:00", "NaT", "2020-01-03"],
+            dtype="datetime64[us]",
+        ),
+    )
+
+    structured = sheet.to_numpy()
+    assert structured.dtype.names == ("A", "B", "C")
+    assert structured["C"][0] == np.datetime64("2020-01-01")
+    # field names of repeated columns are unique
+    assert sheet.to_numpy(usecols=[2, 0, 2]).dtype.names == ("C", "A", "C.1")
+
+    with pytest.raises(ValueError):
+        sheet.to_numpy(usecols="A", dtype="int64")
+    with pytest.raises(ValueError):
+        sheet.to_numpy(dtype="complex128")
+    with pytest.raises(IndexError):
+        sheet.to_numpy(usecols=[5])
+
+
+def test_usecols():
+    reader = CalamineWorkbook.from_object(PATH / "tables.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    assert sheet.to_python(usecols="C:D")[2:] == [
+        ["price", "qty"],
+        [1.5, 10],
+        [2.5, 20],
+        [3.5, ""],
+    ]
+    assert sheet.to_python(usecols=[3, 1], nrows=4)[2:] == [
+        ["qty", "name"],
+        [10, "apple"],
+    ]
+    assert sheet.to_python(usecols="B", skip_empty_area=False)[:3] == [
+        [""],
+        [""],
+        ["name"],
+    ]
+    assert list(sheet.iter_rows(usecols="D,B"))[3] == [10, "apple"]
+
+    assert sheet.to_records(header_row=2, usecols=["qty", "name"]) == [
+        {"qty": 10, "name": "apple"},
+        {"qty": 20, "name": "pear"},
+        {"qty": "", "name": "plum"},
+    ]
+    assert list(sheet.iter_records(header_row=2, usecols="C")) == [
+        {"price": 1.5},
+        {"price": 2.5},
+        {"price": 3.5},
+    ]
+    assert sheet.to_columns(header_row=2, usecols=["price"]) == {
+        "price": [1.5, 2.5, 3.5]
+    }
+    assert sheet.to_arrow(header_row=2, usecols=["name", "qty"]).column_names == [
+        "name",
+        "qty",
+    ]
+
+    with pytest.raises(ValueError):
+        sheet.to_python(usecols="A:")
+    # str is parsed as letters only, up to XFD
+    with pytest.raises(ValueError):
+        sheet.to_python(usecols="XFE")
+    with pytest.raises(ValueError):
+        sheet.to_records(header_row=2, usecols=["weight"])
+
+    # names are taken from the first row of data, not from the padded top
+    sheet = CalamineWorkbook.from_object(PATH / "base.xlsx").get_sheet_by_name("Sheet3")
+    assert sheet.to_python(usecols=["line1.1"], skip_empty_area=False) == [
+        [""],
+        ["line1"],
+        ["line2"],
+        ["line3"],
+    ]
+    with pytest.raises(IndexError):
+        sheet.to_python(usecols=[10])
+
+
+def test_skiprows():
+    reader = CalamineWorkbook.from_object(PATH / "tables.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    assert sheet.to_python(skiprows=2, skipfooter=1) == [
+        ["", "name", "price", "qty"],
+        ["", "apple", 1.5, 10],
+        ["", "pear", 2.5, 20],
+    ]
+    assert sheet.to_python(skiprows=[0, 3], skip_blank_rows=True, usecols="B") == [
+        ["name"],
+        ["pear"],
+        ["plum"],
+    ]
+    assert sheet.to_python(skiprows=lambda index: index % 2 == 1, nrows=2) == [
+        ["Fruits below", "", "", ""],
+        ["", "name", "price", "qty"],
+    ]
+    assert sheet.to_python(skip_blank_rows=True, nrows=2) == [
+        ["Fruits below", "", "", ""],
+        ["", "name", "price", "qty"],
+    ]
+    assert sheet.to_python(skipfooter=10) == []
+
+    assert list(sheet.iter_rows(skiprows=2, skipfooter=2)) == [
+        ["", "name", "price", "qty"],
+        ["", "apple", 1.5, 10],
+    ]
+    assert len(list(sheet.iter_rows(skip_blank_rows=True))) == 5
+
+    with pytest.raises(TypeError):
+        sheet.to_python(skiprows="1")
+
+
+@pytest.mark.parametrize(
+    "obj",
+    [
+        PATH / "base.xls",
+        PATH / "base.xlsx",
+        PATH / "base.xlsb",
+    ],
+)
+def test_dates(obj):
+    reader = CalamineWorkbook.from_object(obj)
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    raw = sheet.to_python(dates="raw")[0][5:8]
+    assert raw[0] == 40461
+    assert raw[1] == pytest.approx(40461.4237268518)
+    assert raw[2] == pytest.approx(0.423726851851852)
+
+    assert sheet.to_python(dates="datetime")[0][5:] == [
+        datetime(2010, 10, 10),
+        datetime(2010, 10, 10, 10, 10, 10),
+        datetime(1899, 12, 31, 10, 10, 10),
+        timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+        timedelta(hours=255, minutes=10, seconds=10),
+    ]
+    assert list(sheet.iter_rows(dates="raw"))[1][5] == 40461
+
+    reader.dates = "raw"
+    assert reader.dates == "raw"
+    sheet = reader.get_sheet_by_name("Sheet1")
+    assert sheet.to_python()[0][5] == 40461
+    assert sheet.to_python(dates="infer")[0][5] == date(2010, 10, 10)
+    assert list(reader.iter_sheet_rows("Sheet1"))[1][5] == 40461
+
+    with pytest.raises(ValueError):
+        reader.dates = "date"
+
+
+def test_dates_ods():
+    reader = CalamineWorkbook.from_object(PATH / "base.ods")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    assert sheet.to_python(dates="raw")[0][5:7] == ["2010-10-10", "2010-10-10T10:10:10"]
+    assert sheet.to_python(dates="datetime")[0][5:7] == [
+        datetime(2010, 10, 10),
+        datetime(2010, 10, 10, 10, 10, 10),
+    ]
+    # times of day are durations in ods
+    assert sheet.to_python(dates="raw")[0][7:] == [
+        "PT10H10M10S",
+        "PT10H10M10.1S",
+        "PT255H10M10S",
+    ]
+    durations = [
+        timedelta(hours=10, minutes=10, seconds=10),
+        timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+        timedelta(hours=255, minutes=10, seconds=10),
+    ]
+    assert sheet.to_python()[0][7:] == durations
+    assert sheet.to_python(dates="datetime")[0][7:] == durations
+
+
+def test_number_formats():
+    reader = CalamineWorkbook.from_object(PATH / "number_formats.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1", number_formats=True)
+
+    assert sheet.number_formats(nrows=2)[1][:5] == [
+        "00000",
+        "0.00%",
+        '"$"#,##0.00_);\\("$"#,##0.00\\)',
+        "yyyy-mm-dd",
+        "@",
+    ]
+    assert sheet.number_formats()[3][:3] == ["General", "General", "General"]
+    assert sheet.number_formats(nrows=2, usecols=[1, 3]) == [
+        ["General", "General"],
+        ["0.00%", "yyyy-mm-dd"],
+    ]
+    assert sheet.to_python(values="formatted") == [
+        ["zip", "percent", "currency", "date", "text", "euro", "time", "duration", "scientific", "sections"],
+        ["02134", "12.34%", "$1,234.50 ", "2010-01-01", "abc", "€ 1,234.57", "6:00 PM", "36:00:00", "1.23E+04", "1,234,567"],
+        ["00501", "100.00%", "($42.00)", "2024-01-01", "42", "-€ 5.00", "7:30 AM", "1:30:00", "1.20E-04", "-1,234"],
+        ["3.5", "", "TRUE", "", "", "", "", "", "", "zero"],
+    ]  # fmt: skip
+    assert sheet.to_python()[1][0] == 2134
+    rows = list(sheet.iter_rows(values="formatted", usecols="A:B"))
+    assert rows[2] == ["00501", "100.00%"]
+    assert sheet.range("B2:C3").to_python(values="formatted") == [
+        ["12.34%", "$1,234.50 "],
+        ["100.00%", "($42.00)"],
+    ]
+
+    with open(PATH / "number_formats.xlsx", "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f)
+    sheet = reader.get_sheet_by_index(0, number_formats=True)
+    assert sheet.to_python(values="formatted", nrows=2)[1][:2] == ["02134", "12.34%"]
+
+
+def test_number_formats_fractions_conditions():
+    reader = CalamineWorkbook.from_object(PATH / "fractions_conditions.xlsx")
+    sheet = reader.get_sheet_by_index(0, number_formats=True)
+
+    assert [row[0] for row in sheet.number_formats()][::5] == [
+        "# ?/?",
+        "# ??/??",
+        "[<1]0.00;[>=1000]#,##0;0",
+        "0.00",
+    ]
+    assert [row[0] for row in sheet.to_python(values="formatted")[:15]] == [
+        "1 1/4",
+        " 1/2",
+        "3    ",
+        "0    ",
+        "-2 3/4",
+        "3 14/99",
+        "1      ",
+        "1/9",
+        "3/2",
+        "2 2/8",
+        "0.50",
+        "1,234",
+        "50",
+        "-5.00",
+        "(5)",
+    ]
+    # precision of seconds is limited to 9 digits
+    assert sheet.to_python(values="formatted")[16] == ["12:00:00.000000000"]
+
+
+def test_number_formats_1904():
+    reader = CalamineWorkbook.from_object(PATH / "dates_1904.xlsx")
+    sheet = reader.get_sheet_by_index(0, number_formats=True)
+
+    assert sheet.to_python() == [
+        [
+            date(1904, 1, 2),
+            datetime(2021, 1, 2, 18),
+            timedelta(days=1, hours=12),
+            42736.0,
+        ]
+    ]
+    # dates are formatted in 1904 date system, durations and numbers are not shifted
+    assert sheet.to_python(values="formatted") == [
+        ["1904-01-02", "2021-01-02 18:00", "36:00", "42736"]
+    ]
+
+
+def test_number_formats_not_loaded():
+    reader = CalamineWorkbook.from_object(PATH / "number_formats.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    with pytest.raises(CalamineError):
+        sheet.number_formats()
+    with pytest.raises(CalamineError):
+        sheet.to_python(values="formatted")
+    with pytest.raises(ValueError):
+        sheet.to_python(values="text")
+    reader = CalamineWorkbook.from_object(PATH / "base.ods")
+    with pytest.raises(CalamineError):
+        reader.get_sheet_by_index(0, number_formats=True)
+
+
+def test_floats_decimal():
+    reader = CalamineWorkbook.from_object(PATH / "number_formats.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    rows = sheet.to_python(floats="decimal")
+    assert rows[1][:3] == [Decimal("2134"), Decimal("0.1234"), Decimal("1234.5")]
+    assert rows[2][8] == Decimal("0.00012")
+    assert rows[3][:3] == [Decimal("3.5"), "", True]
+    assert rows[1][3] == date(2010, 1, 1)
+    assert list(sheet.iter_rows(floats="decimal"))[1][1] == Decimal("0.1234")
+    assert sheet.to_python()[1][1] == 0.1234
+
+    with pytest.raises(ValueError):
+        sheet.to_python(floats="double")
+
+
+@pytest.mark.parametrize(
+    "obj",
+    [
+        PATH / "base.ods",
+        PATH / "base.xls",
+        PATH / "base.xlsx",
+        PATH / "base.xlsb",
+    ],
+)
+def test_numbers(obj):
+    reader = CalamineWorkbook.from_object(obj)
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    ints = sheet.to_python(numbers="int")[0][1:3]
+    assert ints == [1, 1.1]
+    assert type(ints[0]) is int
+    floats = sheet.to_python(numbers="float")[0][1:3]
+    assert floats == [1.0, 1.1]
+    assert type(floats[0]) is float
+    assert list(sheet.iter_rows(numbers="int"))[1][1:3] == [1, 1.1]
+    assert sheet.to_python(numbers="float", floats="decimal")[0][1] == Decimal("1")
+
+    with pytest.raises(ValueError):
+        sheet.to_python(numbers="decimal")
+
+
+@pytest.mark.parametrize("ext", ["ods", "xls", "xlsx", "xlsb"])
+def test_from_path_mmap(ext):
+    path = PATH / f"base.{ext}"
+    eager = CalamineWorkbook.from_path(path)
+
+    for reader in [
+        CalamineWorkbook.from_path(path, mmap=True),
+        CalamineWorkbook.from_object(str(path), mmap=True),
+        load_workbook(path, mmap=True),
+    ]:
+        assert reader.path == str(path)
+        assert reader.sheet_names == eager.sheet_names
+        assert reader.defined_names == eager.defined_names
+        for name in eager.sheet_names:
+            assert (
+                reader.get_sheet_by_name(name).to_python()
+                == eager.get_sheet_by_name(name).to_python()
+            )
+            assert list(reader.iter_sheet_rows(name)) == list(
+                eager.iter_sheet_rows(name)
+            )
+        reader.close()
+
+    with pytest.raises(IOError):
+        CalamineWorkbook.from_path(PATH / "missing.xlsx", mmap=True)
+
+
+@pytest.mark.parametrize("wrap", [bytes, bytearray, memoryview])
+def test_from_bytes(wrap):
+    data = wrap((PATH / "base.xlsx").read_bytes())
+
+    for reader in [
+        CalamineWorkbook.from_bytes(data),
+        CalamineWorkbook.from_object(data),
+        load_workbook(data),
+    ]:
+        assert reader.sheet_names == ["Sheet1", "Sheet2", "Sheet3"]
+        assert reader.get_sheet_by_index(0).to_python()[0][:3] == ["String", 1.0, 1.1]
+        assert list(reader.iter_sheet_rows("Sheet1"))[1][:2] == ["String", 1.0]
+
+
+def test_from_bytes_without_copy():
+    data = bytearray((PATH / "base.xlsx").read_bytes())
+    reader = CalamineWorkbook.from_bytes(memoryview(data).toreadonly())
+
+    # read-only buffer is exported while workbook is open
+    with pytest.raises(BufferError):
+        data.extend(b"\0")
+    reader.close()
+    data.extend(b"\0")
+
+    # writable buffer is copied
+    reader = CalamineWorkbook.from_bytes(data)
+    data[:] = b"\0" * len(data)
+    assert list(reader.iter_sheet_rows("Sheet1"))[1][:2] == ["String", 1.0]
+
+    with pytest.raises(ValueError):
+        CalamineWorkbook.from_bytes(memoryview(data)[::2])
+    with pytest.raises(TypeError):
+        CalamineWorkbook.from_bytes("base.xlsx")
+
+
+def test_from_filelike_lazy():
+    class CountingReader(BytesIO):
+        read_bytes = 0
+
+        def read(self, size=-1):
+            data = super().read(size)
+            self.read_bytes += len(data)
+            return data
+
+    data = (PATH / "base.xlsx").read_bytes()
+    eager = CalamineWorkbook.from_filelike(BytesIO(data))
+
+    with open(PATH / "base.xlsx", "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f, lazy=True)
+        assert reader.sheet_names == eager.sheet_names
+        assert (
+            reader.get_sheet_by_index(0).to_python()
+            == eager.get_sheet_by_index(0).to_python()
+        )
+        assert list(reader.iter_sheet_rows("Sheet1")) == list(
+            eager.iter_sheet_rows("Sheet1")
+        )
+
+    # parts not needed for reading sheets are not read
+    padded = BytesIO(data)
+    with zipfile.ZipFile(padded, "a") as zf:
+        zf.writestr("xl/media/padding.bin", bytes(1024 * 1024))
+    f = CountingReader(padded.getvalue())
+    reader = CalamineWorkbook.from_filelike(f, lazy=True)
+    assert reader.get_sheet_by_name("Sheet3").to_python()[0] == ["line1"] * 3
+    assert f.read_bytes < 1024 * 1024
+
+    with pytest.raises(TypeError):
+        CalamineWorkbook.from_filelike(object(), lazy=True)
+
+
+def test_from_filelike_lazy_short_reads():
+    class ShortReader(BytesIO):
+        def read(self, size=-1):
+            return super().read(min(size, 100) if size >= 0 else size)
+
+    f = ShortReader(b"header" + (PATH / "base.xlsx").read_bytes())
+    f.seek(6)
+    reader = CalamineWorkbook.from_filelike(f, lazy=True)
+    assert reader.get_sheet_by_index(0).to_python()[0][:3] == ["String", 1.0, 1.1]
+    # position of caller is restored
+    assert f.tell() == 6
+
+
+def test_from_filelike_lazy_start_position():
+    class BytearrayReader(BytesIO):
+        def read(self, size=-1):
+            return bytearray(super().read(size))
+
+    class MemoryviewReader(BytesIO):
+        def read(self, size=-1):
+            return memoryview(super().read(size))
+
+    data = (PATH / "base.xlsx").read_bytes()
+    expected = CalamineWorkbook.from_bytes(data).get_sheet_by_index(0).to_python()
+    # like eager reading, workbook starts at the current position of the object
+    for lazy in [False, True]:
+        f = BytesIO(b"\0" * 100 + data)
+        f.seek(100)
+        reader = CalamineWorkbook.from_filelike(f, lazy=lazy)
+        assert reader.get_sheet_by_index(0).to_python() == expected
+
+    # `read` may return any buffer
+    for reader_class in [BytearrayReader, MemoryviewReader]:
+        reader = CalamineWorkbook.from_filelike(reader_class(data), lazy=True)
+        assert reader.get_sheet_by_index(0).to_python() == expected
+
+
+def test_format(tmp_path):
+    path = tmp_path / "export.xls"
+    path.write_bytes((PATH / "base.xlsx").read_bytes())
+    expected = CalamineWorkbook.from_path(PATH / "base.xlsx")
+
+    # format is detected the same way with and without mmap: by extension,
+    # for unknown extensions by trying every format
+    for mmap in [False, True]:
+        with pytest.raises(CalamineError):
+            CalamineWorkbook.from_path(path, mmap=mmap)
+    unknown = tmp_path / "export.bin"
+    unknown.write_bytes(path.read_bytes())
+    garbage = tmp_path / "garbage.bin"
+    garbage.write_bytes(b"not a workbook")
+    for mmap in [False, True]:
+        assert CalamineWorkbook.from_path(unknown, mmap=mmap).sheet_names == (
+            expected.sheet_names
+        )
+        with pytest.raises(CalamineError, match="Cannot detect file format"):
+            CalamineWorkbook.from_path(garbage, mmap=mmap)
+
+    with open(path, "rb") as f:
+        for reader in [
+            CalamineWorkbook.from_path(path, format="xlsx"),
+            CalamineWorkbook.from_path(path, mmap=True, format="xlsm"),
+            CalamineWorkbook.from_object(path, format="xlsx"),
+            CalamineWorkbook.from_filelike(f, lazy=True, format="xlsx"),
+            CalamineWorkbook.from_bytes(path.read_bytes(), format="xlsx"),
+            load_workbook(BytesIO(path.read_bytes()), format="xlsx"),
+        ]:
+            assert reader.sheet_names == expected.sheet_names
+            assert (
+                reader.get_sheet_by_index(0).to_python()
+                == expected.get_sheet_by_index(0).to_python()
+            )
+            assert list(reader.iter_sheet_rows("Sheet1")) == list(
+                expected.iter_sheet_rows("Sheet1")
+            )
+
+    with pytest.raises(CalamineError):
+        CalamineWorkbook.from_bytes((PATH / "base.xlsx").read_bytes(), format="ods")
+    with pytest.raises(ValueError):
+        CalamineWorkbook.from_path(PATH / "base.xlsx", format="csv")
+
+
+@pytest.mark.parametrize(
+    "path, expected",
+    [
+        ("base.xlsx", DetectedFormat("xlsx")),
+        ("base.xlsb", DetectedFormat("xlsb")),
+        ("base.xls", DetectedFormat("xls", 8)),
+        ("base.ods", DetectedFormat("ods")),
+        ("password.xlsx", DetectedFormat(encrypted=True)),
+        ("password.xlsb", DetectedFormat(encrypted=True)),
+        ("password.xls", DetectedFormat("xls", 8)),
+        ("empty_file.xlsx", DetectedFormat()),
+    ],
+)
+def test_detect_format(path, expected):
+    assert detect_format(PATH / path) == expected
+    assert detect_format(str(PATH / path)) == expected
+    assert detect_format((PATH / path).read_bytes()) == expected
+
+
+def test_detect_format_content():
+    data = BytesIO()
+    with zipfile.ZipFile(PATH / "base.xlsx") as src, zipfile.ZipFile(data, "w") as dst:
+        for item in src.infolist():
+            content = src.read(item)
+            if item.filename == "[Content_Types].xml":
+                content = content.replace(
+                    b"spreadsheetml.sheet.main+xml",
+                    b"ms-excel.sheet.macroEnabled.main+xml",
+                )
+            dst.writestr(item, content)
+    assert detect_format(data.getvalue()) == DetectedFormat("xlsm")
+
+    # BIFF4 files aren't wrapped into CFB
+    biff4 = b"\x09\x04\x06\x00\x00\x00\x00\x01"
+    assert detect_format(biff4) == DetectedFormat("xls", 4)
+    assert detect_format(b"") == DetectedFormat()
+    assert detect_format(b"PK\x03\x04broken") == DetectedFormat()
+    assert (
+        repr(DetectedFormat("xls", 8)) == "DetectedFormat(format='xls', biff_version=8)"
+    )
+    assert repr(DetectedFormat(encrypted=True)) == (
+        "DetectedFormat(format=None, encrypted=True)"
+    )
+
+    # detected format is accepted by `format`
+    for path in ["base.xlsx", "base.xlsb", "base.xls", "base.ods"]:
+        data = (PATH / path).read_bytes()
+        reader = CalamineWorkbook.from_bytes(data, format=detect_format(data).format)
+        assert reader.sheet_names[0] == "Sheet1"
+    with pytest.raises(ValueError):
+        DetectedFormat("unknown")
+
+    with pytest.raises(IOError):
+        detect_format(PATH / "missing.xlsx")
+    with pytest.raises(TypeError):
+        detect_format(1)
+
+
+def test_detect_format_broken_cfb():
+    # huge count of FAT sectors and DIFAT sector linked to itself
+    header = bytearray(512)
+    header[:8] = b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1"
+    header[0x1E:0x20] = (9).to_bytes(2, "little")
+    header[0x2C:0x30] = b"\xff" * 4
+    header[0x4C:] = b"\xff" * (512 - 0x4C)
+    for sectors in [1, 1000]:
+        # the 1 KB file and a larger one with the DIFAT loop reached
+        data = bytes(header) + bytes(512 * sectors)
+        assert detect_format(data) == DetectedFormat()
+
+    def entry(name, start, size):
+        encoded = (name + "\0").encode("utf-16-le")
+        data = bytearray(128)
+        data[: len(encoded)] = encoded
+        data[64:66] = len(encoded).to_bytes(2, "little")
+        data[116:120] = start.to_bytes(4, "little")
+        data[120:124] = size.to_bytes(4, "little")
+        return bytes(data)
+
+    def cfb(fat, workbook_start, root_start=2):
+        # FAT in sector 0, directory in sector 1, small workbook stream in mini sectors
+        header = bytearray(512)
+        header[:8] = b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1"
+        header[0x1E:0x20] = (9).to_bytes(2, "little")
+        header[0x2C:0x30] = (1).to_bytes(4, "little")
+        header[0x30:0x34] = (1).to_bytes(4, "little")
+        header[0x38:0x3C] = (4096).to_bytes(4, "little")
+        header[0x44:0x48] = b"\xfe\xff\xff\xff"
+        header[0x4C:] = b"\xff" * (512 - 0x4C)
+        header[0x4C:0x50] = (0).to_bytes(4, "little")
+        fat_sector = b"".join(n.to_bytes(4, "little") for n in fat)
+        fat_sector += b"\xff" * (512 - len(fat_sector))
+        directory = entry("Root Entry", root_start, 512) + entry(
+            "Workbook", workbook_start, 100
+        )
+        directory += bytes(512 - len(directory))
+        bof = b"\x09\x08\x10\x00\x00\x06\x05\x00"
+        return bytes(header) + fat_sector + directory + bof + bytes(512 - len(bof))
+
+    end, fat_sect = 0xFFFFFFFE, 0xFFFFFFFD
+    assert detect_format(cfb([fat_sect, end, end], 0)) == DetectedFormat("xls", 8)
+    # directory and mini stream chains linked to themselves, mini sector far after
+    # the end of chain
+    assert detect_format(cfb([fat_sect, 1, end], 0)) == DetectedFormat()
+    assert detect_format(cfb([fat_sect, end, 2], 8)) == DetectedFormat()
+    assert detect_format(cfb([fat_sect, end, end], 1_000_000_000)) == DetectedFormat()
+
+
+def test_error_cells():
+    reader = CalamineWorkbook.from_object(PATH / "errors.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    assert sheet.to_python() == [
+        ["Formula", "Result"],
+        ["=1/0", CellError(CellErrorTypeEnum.Div0, "#DIV/0!")],
+        ["=NA()", CellError(CellErrorTypeEnum.NA, "#N/A")],
+        ["=A1+#REF!", CellError(CellErrorTypeEnum.Ref, "#REF!")],
+        ["=1+1", 2],
+    ]
+    assert sheet.to_python(errors="none") == [
+        ["Formula", "Result"],
+        ["=1/0", None],
+        ["=NA()", None],
+        ["=A1+#REF!", None],
+        ["=1+1", 2],
+    ]
+    assert list(sheet.iter_rows(errors="string")) == [
+        ["Formula", "Result"],
+        ["=1/0", "#DIV/0!"],
+        ["=NA()", "#N/A"],
+        ["=A1+#REF!", "#REF!"],
+        ["=1+1", 2],
+    ]
+
+
+def test_error_cells_raise():
+    reader = CalamineWorkbook.from_object(PATH / "errors.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    with pytest.raises(CellValueError, match=r"\(1, 1\).*#DIV/0!"):
+        sheet.to_python(errors="raise")
+
+    with pytest.raises(CellValueError):
+        list(sheet.iter_rows(errors="raise"))
+
+    with pytest.raises(ValueError):
+        sheet.to_python(errors="unknown")
+
+
+def test_formulas():
+    reader = CalamineWorkbook.from_object(PATH / "formulas.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1", formulas=True)
+
+    assert sheet.to_python() == [
+        [1, 2, 3],
+        ["a", "b", "ab"],
+        [3, True, ""],
+    ]
+    assert sheet.formulas() == [
+        [None, None, "A1+B1"],
+        [None, None, "CONCATENATE(A2,B2)"],
+        ["SUM(A1:B1)", "C1>2", None],
+    ]
+    assert sheet.formulas(nrows=1) == [
+        [None, None, "A1+B1"],
+    ]
+    assert sheet.formulas(usecols="C") == [
+        ["A1+B1"],
+        ["CONCATENATE(A2,B2)"],
+        [None],
+    ]
+
+
+def test_formulas_not_loaded():
+    reader = CalamineWorkbook.from_object(PATH / "formulas.xlsx")
+    sheet = reader.get_sheet_by_index(0)
+
+    with pytest.raises(CalamineError):
+        sheet.formulas()
+
+
+def test_merged_cells():
+    reader = CalamineWorkbook.from_object(PATH / "merged_cells.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1", merged_cells=True)
+
+    assert sheet.merged_cells == [((0, 0), (0, 1)), ((0, 2), (0, 3)), ((3, 0), (4, 1))]
+    assert sheet.to_python() == [
+        ["Q1", "", "Q2", ""],
+        ["Jan", "Feb", "Apr", "May"],
+        [1, 2, 3, 4],
+        ["Total", "", 10, ""],
+    ]
+    assert sheet.to_python(fill_merged=True) == [
+        ["Q1", "Q1", "Q2", "Q2"],
+        ["Jan", "Feb", "Apr", "May"],
+        [1, 2, 3, 4],
+        ["Total", "Total", 10, ""],
+    ]
+    assert list(sheet.iter_rows(fill_merged=True)) == sheet.to_python(fill_merged=True)
+
+
+@pytest.mark.parametrize(
+    "path, expected",
+    [
+        (PATH / "base.xls", []),
+        (PATH / "base.xlsx", []),
+        (PATH / "base.xlsb", []),
+        (PATH / "base.ods", []),
+        (
+            PATH / "merged_cells.xlsb",
+            [((0, 0), (0, 1)), ((0, 2), (0, 3)), ((3, 0), (4, 1))],
+        ),
+        (
+            PATH / "merged_cells.ods",
+            [((0, 0), (0, 1)), ((0, 2), (0, 3)), ((3, 0), (4, 1))],
+        ),
+    ],
+)
+def test_merged_cells_formats(path, expected):
+    reader = CalamineWorkbook.from_object(path)
+    assert reader.get_sheet_by_index(0, merged_cells=True).merged_cells == expected
+
+
+def test_merged_cells_from_filelike():
+    with open(PATH / "merged_cells.ods", "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f)
+        sheet = reader.get_sheet_by_index(0, merged_cells=True)
+
+    assert sheet.to_python(fill_merged=True) == [
+        ["Q1", "Q1", "Q2", "Q2"],
+        ["Jan", "Feb", "Apr", "May"],
+        [1, 2, 3, 4],
+        ["Total", "Total", 10, ""],
+    ]
+
+
+def test_merged_cells_not_loaded():
+    reader = CalamineWorkbook.from_object(PATH / "merged_cells.xlsx")
+    sheet = reader.get_sheet_by_index(0)
+
+    with pytest.raises(CalamineError):
+        sheet.merged_cells
+
+    with pytest.raises(CalamineError):
+        sheet.to_python(fill_merged=True)
+
+
+def test_to_arrow():
+    reader = CalamineWorkbook.from_object(PATH / "merged_cells.xlsx")
+    sheet = reader.get_sheet_by_index(0)
+
+    table = sheet.to_arrow()
+    assert table.num_rows == 3
+    assert table.column_names == ["Q1", "Unnamed: 1", "Q2", "Unnamed: 3"]
+
+    table = sheet.to_arrow(header_row=None)
+    assert table.num_rows == 4
+    assert table.column_names == ["column_0", "column_1", "column_2", "column_3"]
+
+
+def test_to_arrow_pyarrow():
+    pa = pytest.importorskip("pyarrow")
+
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_index(0)
+
+    table = pa.table(sheet.to_arrow(header_row=None))
+    assert table.column_names == [f"column_{i}" for i in range(10)]
+    assert table.schema.types == [
+        pa.string(),
+        pa.float64(),
+        pa.float64(),
+        pa.bool_(),
+        pa.bool_(),
+        pa.date32(),
+        pa.timestamp("us"),
+        pa.time64("us"),
+        pa.duration("us"),
+        pa.duration("us"),
+    ]
+    assert table.to_pylist() == [
+        {
+            "column_0": "String",
+            "column_1": 1.0,
+            "column_2": 1.1,
+            "column_3": True,
+            "column_4": False,
+            "column_5": date(2010, 10, 10),
+            "column_6": datetime(2010, 10, 10, 10, 10, 10),
+            "column_7": time(10, 10, 10),
+            "column_8": timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+            "column_9": timedelta(hours=255, minutes=10, seconds=10),
+        }
+    ]
+
+    # sheet itself uses the first row as header
+    assert pa.table(sheet).num_rows == 0
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.ods",
+        PATH / "base.xls",
+        PATH / "base.xlsb",
+        PATH / "base.xlsx",
+    ],
+)
+def test_iter_sheet_rows(path):
+    reader = CalamineWorkbook.from_path(path)
+    expected = reader.get_sheet_by_name("Sheet1").to_python(skip_empty_area=False)
+    assert list(reader.iter_sheet_rows("Sheet1")) == expected
+
+    with open(path, "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f)
+    assert list(reader.iter_sheet_rows("Sheet1")) == expected
+
+
+@pytest.mark.parametrize("dimension", [b"", b'<dimension ref="A1:B2"/>'])
+def test_iter_sheet_rows_without_dimension(tmp_path, dimension):
+    # first row is narrower than the next ones, cells outside of dimension widen rows
+    path = tmp_path / "no_dimension.xlsx"
+    with zipfile.ZipFile(PATH / "merged_cells.xlsx") as src, zipfile.ZipFile(
+        path, "w"
+    ) as dst:
+        for item in src.infolist():
+            data = src.read(item)
+            if item.filename == "xl/worksheets/sheet1.xml":
+                data = re.sub(rb"<dimension [^>]*/>", dimension, data)
+                data = re.sub(rb'<c r="C1".*?</c>', b"", data)
+            dst.writestr(item, data)
+
+    reader = CalamineWorkbook.from_path(path)
+    rows = list(reader.iter_sheet_rows("Sheet1"))
+    expected = reader.get_sheet_by_name("Sheet1").to_python(skip_empty_area=False)
+    assert rows == [expected[0][: len(rows[0])]] + expected[1:]
+    assert [len(row) for row in rows] == [len(rows[0]), 4, 4, 4]
+
+
+def test_iter_sheet_rows_options():
+    reader = CalamineWorkbook.from_path(PATH / "errors.xlsx")
+    expected = reader.get_sheet_by_index(0).to_python(
+        skip_empty_area=False, errors="string", empty_value=None
+    )
+    assert (
+        list(reader.iter_sheet_rows("Sheet1", errors="string", empty_value=None))
+        == expected
+    )
+
+    with pytest.raises(CellValueError):
+        list(reader.iter_sheet_rows("Sheet1", errors="raise"))
+
+    with pytest.raises(WorksheetNotFound):
+        reader.iter_sheet_rows("NotFound")
+
+
+def test_iter_sheet_rows_usecols():
+    reader = CalamineWorkbook.from_path(PATH / "merged_cells.xlsx")
+
+    assert list(reader.iter_sheet_rows("Sheet1", usecols="B:C")) == [
+        ["", "Q2"],
+        ["Feb", "Apr"],
+        [2, 3],
+        ["", 10],
+    ]
+    assert list(reader.iter_sheet_rows("Sheet1", usecols=["Q2", "Q1"])) == [
+        ["Q2", "Q1"],
+        ["Apr", "Jan"],
+        [3, 1],
+        [10, "Total"],
+    ]
+
+    with pytest.raises(IndexError):
+        list(reader.iter_sheet_rows("Sheet1", usecols=[4]))
+
+
+def test_empty_value():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.to_python(skip_empty_area=False, empty_value=None) == [
+        [None, None, None, None],
+        [None, "line1", "line1", "line1"],
+        [None, "line2", "line2", "line2"],
+        [None, "line3", "line3", "line3"],
+    ]
+
+    sentinel = object()
+    assert list(sheet.iter_rows(empty_value=sentinel)) == [
+        [sentinel, sentinel, sentinel],
+        ["line1", "line1", "line1"],
+        ["line2", "line2", "line2"],
+        ["line3", "line3", "line3"],
+    ]
+
+
+def test_workbook_empty_value():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    assert reader.empty_value == ""
+
+    reader.empty_value = None
+    assert reader.empty_value is None
+
+    sheet = reader.get_sheet_by_name("Sheet3")
+    assert sheet.to_python(skip_empty_area=False, nrows=2) == [
+        [None, None, None, None],
+        [None, "line1", "line1", "line1"],
+    ]
+    assert sheet.to_python(skip_empty_area=False, nrows=2, empty_value="") == [
+        ["", "", "", ""],
+        ["", "line1", "line1", "line1"],
+    ]
+
+
 @pytest.mark.parametrize(
     "path",
     [